@@ -0,0 +1,74 @@
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use crate::error::Error;
+use crate::escape::escape_into;
+use crate::float;
+use crate::format::Format;
+use crate::json::Json;
+use crate::number::Number;
+
+// Writes `json` following the JSON Canonicalization Scheme, RFC 8785.
+pub fn canonicalize<W: Write>(writer: &mut W, json: &Json) -> Result<(), Error>
+{
+    match json {
+        Json::Null => {
+            writer.write_str("null")?;
+        },
+        Json::Boolean(value) => {
+            writer.write_str(if *value { "true" } else { "false" })?;
+        },
+        Json::Number(ref value) => {
+            // Numbers are IEEE 754 doubles in I-JSON, integers included.
+            let value = match value {
+                Number::Unsigned(value) => *value as f64,
+                Number::Integer(value)  => *value as f64,
+                Number::Float(value)    => *value,
+            };
+
+            if !value.is_finite() {
+                return Err(Error::NonFiniteNumber(Number::Float(value).to_string()));
+            }
+
+            writer.write_str(&float::ecmascript(value))?;
+        },
+        Json::String(ref value) => {
+            // The default format only escapes what JSON requires.
+            escape_into(writer, value, &Format::default())?;
+        },
+        Json::Array(ref value) => {
+            writer.write_char('[')?;
+            for (i, elem) in value.iter().enumerate() {
+                if i > 0 {
+                    writer.write_char(',')?;
+                }
+                canonicalize(writer, elem)?;
+            }
+            writer.write_char(']')?;
+        },
+        Json::Object(ref value) => {
+            let mut entries: Vec<(&String, &Json)> = value.iter().collect();
+            entries.sort_by(|a, b| compare_utf16(a.0, b.0));
+
+            writer.write_char('{')?;
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    writer.write_char(',')?;
+                }
+                escape_into(writer, k, &Format::default())?;
+                writer.write_char(':')?;
+                canonicalize(writer, v)?;
+            }
+            writer.write_char('}')?;
+        },
+    }
+
+    Ok(())
+}
+
+// Keys are sorted by their UTF-16 code units, which differs from sorting by
+// code points for characters outside the Basic Multilingual Plane.
+fn compare_utf16(a: &str, b: &str) -> Ordering
+{
+    a.encode_utf16().cmp(b.encode_utf16())
+}
@@ -0,0 +1,119 @@
+use std::env;
+use std::fmt::Write;
+use std::io::{ self, IsTerminal };
+
+use crate::error::Error;
+use crate::format::Format;
+
+/// ANSI styles used to color each kind of token, given as SGR parameters:
+/// the part between `ESC[` and `m`, like `"1;34"` for bold blue. An empty
+/// string leaves that kind of token alone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub key:         String,
+    pub string:      String,
+    pub number:      String,
+    pub boolean:     String,
+    pub null:        String,
+    /// Brackets, braces, commas and colons.
+    pub punctuation: String,
+}
+
+impl Palette {
+    pub fn new() -> Palette
+    {
+        Palette {
+            key:         String::from("1;34"),
+            string:      String::from("32"),
+            number:      String::from("36"),
+            boolean:     String::from("33"),
+            null:        String::from("90"),
+            punctuation: String::from("1"),
+        }
+    }
+
+    /// The default palette if standard output is a terminal and `NO_COLOR`
+    /// isn't set, `None` otherwise. Meant for `Format::colors`.
+    pub fn auto() -> Option<Palette>
+    {
+        Palette::auto_for(&io::stdout())
+    }
+
+    /// Same as `auto`, checking `stream` instead of standard output.
+    pub fn auto_for<T: IsTerminal>(stream: &T) -> Option<Palette>
+    {
+        // See https://no-color.org, an empty value doesn't count.
+        let disabled = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+        if disabled || !stream.is_terminal() {
+            return None;
+        }
+
+        Some(Palette::new())
+    }
+}
+
+impl Default for Palette
+{
+    fn default() -> Palette
+    {
+        Palette::new()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Role {
+    Key,
+    String,
+    Number,
+    Boolean,
+    Null,
+    Punctuation,
+}
+
+// Style of `role` in the palette of `format`, empty without colors.
+pub fn style(format: &Format, role: Role) -> &str
+{
+    let palette = match format.colors {
+        Some(ref palette) => palette,
+        None              => { return ""; },
+    };
+
+    match role {
+        Role::Key         => &palette.key,
+        Role::String      => &palette.string,
+        Role::Number      => &palette.number,
+        Role::Boolean     => &palette.boolean,
+        Role::Null        => &palette.null,
+        Role::Punctuation => &palette.punctuation,
+    }
+}
+
+// Runs `callback` between the escape sequences setting and resetting `role`.
+pub fn paint<W, F>(writer: &mut W, format: &Format, role: Role, callback: F) -> Result<(), Error>
+    where W: Write,
+          F: FnOnce(&mut W) -> Result<(), Error>
+{
+    let style = style(format, role);
+
+    if style.is_empty() {
+        return callback(writer);
+    }
+
+    write!(writer, "\x1b[{}m", style)?;
+    callback(writer)?;
+    writer.write_str("\x1b[0m")?;
+
+    Ok(())
+}
+
+pub fn punctuation<W: Write>(writer: &mut W, format: &Format, text: &str) -> std::fmt::Result
+{
+    let style = style(format, Role::Punctuation);
+
+    if style.is_empty() {
+        return writer.write_str(text);
+    }
+
+    write!(writer, "\x1b[{}m{}\x1b[0m", style, text)
+}
@@ -0,0 +1,565 @@
+use std::collections::{ BTreeMap, HashMap, HashSet };
+use std::convert::{ Infallible, TryFrom };
+use std::hash::Hash;
+
+use crate::error::Error;
+use crate::json::Json;
+use crate::number::Number;
+
+// Conversions between `Json` and Rust types. Going into `Json` never fails,
+// coming out of it returns `Error::InvalidType` for the wrong kind of value,
+// `Error::OutOfRange` for numbers that don't fit and `Error::InvalidLength`
+// for arrays of the wrong size.
+
+impl From<Infallible> for Error
+{
+    fn from(error: Infallible) -> Error
+    {
+        match error {}
+    }
+}
+
+// Name of the variant, for error messages.
+pub fn kind(json: &Json) -> &'static str
+{
+    match json {
+        Json::Object(_)  => "object",
+        Json::Array(_)   => "array",
+        Json::String(_)  => "string",
+        Json::Number(_)  => "number",
+        Json::Boolean(_) => "boolean",
+        Json::Null       => "null",
+    }
+}
+
+impl<'a> From<&'a Json> for Json
+{
+    fn from(json: &'a Json) -> Json
+    {
+        json.clone()
+    }
+}
+
+impl From<Number> for Json
+{
+    fn from(number: Number) -> Json
+    {
+        Json::Number(number)
+    }
+}
+
+macro_rules! numbers {
+    ($($t:ident),*) => {$(
+        impl From<$t> for Json
+        {
+            fn from(number: $t) -> Json
+            {
+                Json::Number(Number::from(number))
+            }
+        }
+
+        impl TryFrom<Json> for $t
+        {
+            type Error = Error;
+
+            fn try_from(json: Json) -> Result<$t, Error>
+            {
+                $t::try_from(&json)
+            }
+        }
+
+        impl<'a> TryFrom<&'a Json> for $t
+        {
+            type Error = Error;
+
+            fn try_from(json: &'a Json) -> Result<$t, Error>
+            {
+                match json {
+                    Json::Number(value) => Ok($t::try_from(value)?),
+                    _ => Err(Error::InvalidType("number", kind(json))),
+                }
+            }
+        }
+    )*};
+}
+
+numbers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl From<bool> for Json
+{
+    fn from(value: bool) -> Json
+    {
+        Json::Boolean(value)
+    }
+}
+
+impl TryFrom<Json> for bool
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<bool, Error>
+    {
+        bool::try_from(&json)
+    }
+}
+
+impl<'a> TryFrom<&'a Json> for bool
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<bool, Error>
+    {
+        match json {
+            Json::Boolean(value) => Ok(*value),
+            _ => Err(Error::InvalidType("boolean", kind(json))),
+        }
+    }
+}
+
+impl From<()> for Json
+{
+    fn from(_: ()) -> Json
+    {
+        Json::Null
+    }
+}
+
+impl TryFrom<Json> for ()
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<(), Error>
+    {
+        <()>::try_from(&json)
+    }
+}
+
+impl<'a> TryFrom<&'a Json> for ()
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<(), Error>
+    {
+        match json {
+            Json::Null => Ok(()),
+            _ => Err(Error::InvalidType("null", kind(json))),
+        }
+    }
+}
+
+impl From<String> for Json
+{
+    fn from(string: String) -> Json
+    {
+        Json::String(string)
+    }
+}
+
+impl<'a> From<&'a str> for Json
+{
+    fn from(string: &'a str) -> Json
+    {
+        Json::String(String::from(string))
+    }
+}
+
+impl From<char> for Json
+{
+    fn from(chr: char) -> Json
+    {
+        Json::String(chr.to_string())
+    }
+}
+
+impl TryFrom<Json> for String
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<String, Error>
+    {
+        match json {
+            Json::String(value) => Ok(value),
+            _ => Err(Error::InvalidType("string", kind(&json))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Json> for String
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<String, Error>
+    {
+        <&str>::try_from(json).map(String::from)
+    }
+}
+
+impl<'a> TryFrom<&'a Json> for &'a str
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<&'a str, Error>
+    {
+        match json {
+            Json::String(value) => Ok(value),
+            _ => Err(Error::InvalidType("string", kind(json))),
+        }
+    }
+}
+
+impl TryFrom<Json> for char
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<char, Error>
+    {
+        char::try_from(&json)
+    }
+}
+
+// Strings holding exactly one character.
+impl<'a> TryFrom<&'a Json> for char
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<char, Error>
+    {
+        let string = <&str>::try_from(json)?;
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(chr), None) => Ok(chr),
+            _ => Err(Error::InvalidLength(1, string.chars().count())),
+        }
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json
+{
+    fn from(vector: Vec<T>) -> Json
+    {
+        Json::Array(vector.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Json>, const N: usize> From<[T; N]> for Json
+{
+    fn from(array: [T; N]) -> Json
+    {
+        Json::from(Vec::from(array))
+    }
+}
+
+impl<T: Into<Json>> From<HashSet<T>> for Json
+{
+    fn from(set: HashSet<T>) -> Json
+    {
+        Json::Array(set.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T> TryFrom<Json> for Vec<T>
+    where T: TryFrom<Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<Vec<T>, Error>
+    {
+        match json {
+            Json::Array(value) => value.into_iter().map(|v| Ok(T::try_from(v)?)).collect(),
+            _ => Err(Error::InvalidType("array", kind(&json))),
+        }
+    }
+}
+
+impl<'a, T> TryFrom<&'a Json> for Vec<T>
+    where T: TryFrom<&'a Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<Vec<T>, Error>
+    {
+        match json {
+            Json::Array(value) => value.iter().map(|v| Ok(T::try_from(v)?)).collect(),
+            _ => Err(Error::InvalidType("array", kind(json))),
+        }
+    }
+}
+
+impl<T, const N: usize> TryFrom<Json> for [T; N]
+    where T: TryFrom<Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<[T; N], Error>
+    {
+        let vector = Vec::<T>::try_from(json)?;
+        let length = vector.len();
+
+        <[T; N]>::try_from(vector).map_err(|_| Error::InvalidLength(N, length))
+    }
+}
+
+impl<'a, T, const N: usize> TryFrom<&'a Json> for [T; N]
+    where T: TryFrom<&'a Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<[T; N], Error>
+    {
+        let vector = Vec::<T>::try_from(json)?;
+        let length = vector.len();
+
+        <[T; N]>::try_from(vector).map_err(|_| Error::InvalidLength(N, length))
+    }
+}
+
+// Duplicate elements are merged.
+impl<T> TryFrom<Json> for HashSet<T>
+    where T: TryFrom<Json> + Eq + Hash, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<HashSet<T>, Error>
+    {
+        match json {
+            Json::Array(value) => value.into_iter().map(|v| Ok(T::try_from(v)?)).collect(),
+            _ => Err(Error::InvalidType("array", kind(&json))),
+        }
+    }
+}
+
+impl<'a, T> TryFrom<&'a Json> for HashSet<T>
+    where T: TryFrom<&'a Json> + Eq + Hash, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<HashSet<T>, Error>
+    {
+        match json {
+            Json::Array(value) => value.iter().map(|v| Ok(T::try_from(v)?)).collect(),
+            _ => Err(Error::InvalidType("array", kind(json))),
+        }
+    }
+}
+
+impl<T: Into<Json>> From<HashMap<String, T>> for Json
+{
+    fn from(map: HashMap<String, T>) -> Json
+    {
+        Json::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl<T: Into<Json>> From<BTreeMap<String, T>> for Json
+{
+    fn from(map: BTreeMap<String, T>) -> Json
+    {
+        Json::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl<T> TryFrom<Json> for HashMap<String, T>
+    where T: TryFrom<Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<HashMap<String, T>, Error>
+    {
+        match json {
+            Json::Object(value) => value.into_iter().map(|(k, v)| Ok((k, T::try_from(v)?))).collect(),
+            _ => Err(Error::InvalidType("object", kind(&json))),
+        }
+    }
+}
+
+impl<'a, T> TryFrom<&'a Json> for HashMap<String, T>
+    where T: TryFrom<&'a Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<HashMap<String, T>, Error>
+    {
+        match json {
+            Json::Object(value) => value.iter().map(|(k, v)| Ok((k.clone(), T::try_from(v)?))).collect(),
+            _ => Err(Error::InvalidType("object", kind(json))),
+        }
+    }
+}
+
+impl<T> TryFrom<Json> for BTreeMap<String, T>
+    where T: TryFrom<Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: Json) -> Result<BTreeMap<String, T>, Error>
+    {
+        match json {
+            Json::Object(value) => value.into_iter().map(|(k, v)| Ok((k, T::try_from(v)?))).collect(),
+            _ => Err(Error::InvalidType("object", kind(&json))),
+        }
+    }
+}
+
+impl<'a, T> TryFrom<&'a Json> for BTreeMap<String, T>
+    where T: TryFrom<&'a Json>, Error: From<T::Error>
+{
+    type Error = Error;
+
+    fn try_from(json: &'a Json) -> Result<BTreeMap<String, T>, Error>
+    {
+        match json {
+            Json::Object(value) => value.iter().map(|(k, v)| Ok((k.clone(), T::try_from(v)?))).collect(),
+            _ => Err(Error::InvalidType("object", kind(json))),
+        }
+    }
+}
+
+// `None` is written as `null`.
+impl<T: Into<Json>> From<Option<T>> for Json
+{
+    fn from(option: Option<T>) -> Json
+    {
+        match option {
+            Some(value) => value.into(),
+            None        => Json::Null,
+        }
+    }
+}
+
+impl<T: Into<Json>> From<Box<T>> for Json
+{
+    fn from(boxed: Box<T>) -> Json
+    {
+        (*boxed).into()
+    }
+}
+
+// The standard library already converts any `T` into `Option<T>` and
+// `Box<T>`, which rules out generic impls covering `T = Json`. These are
+// written out for every other type instead, generic over what containers
+// hold.
+macro_rules! wrappers {
+    ($([$($generics:tt)*] $t:ty),* $(,)*) => {$(
+        impl<$($generics)*> TryFrom<Json> for Option<$t>
+            where $t: TryFrom<Json, Error = Error>
+        {
+            type Error = Error;
+
+            fn try_from(json: Json) -> Result<Option<$t>, Error>
+            {
+                match json {
+                    Json::Null => Ok(None),
+                    _ => Ok(Some(<$t>::try_from(json)?)),
+                }
+            }
+        }
+
+        impl<'a, $($generics)*> TryFrom<&'a Json> for Option<$t>
+            where $t: TryFrom<&'a Json, Error = Error>
+        {
+            type Error = Error;
+
+            fn try_from(json: &'a Json) -> Result<Option<$t>, Error>
+            {
+                match json {
+                    Json::Null => Ok(None),
+                    _ => Ok(Some(<$t>::try_from(json)?)),
+                }
+            }
+        }
+
+        impl<$($generics)*> TryFrom<Json> for Box<$t>
+            where $t: TryFrom<Json, Error = Error>
+        {
+            type Error = Error;
+
+            fn try_from(json: Json) -> Result<Box<$t>, Error>
+            {
+                Ok(Box::new(<$t>::try_from(json)?))
+            }
+        }
+
+        impl<'a, $($generics)*> TryFrom<&'a Json> for Box<$t>
+            where $t: TryFrom<&'a Json, Error = Error>
+        {
+            type Error = Error;
+
+            fn try_from(json: &'a Json) -> Result<Box<$t>, Error>
+            {
+                Ok(Box::new(<$t>::try_from(json)?))
+            }
+        }
+    )*};
+}
+
+wrappers! {
+    [] u8, [] u16, [] u32, [] u64, [] usize,
+    [] i8, [] i16, [] i32, [] i64, [] isize,
+    [] f32, [] f64, [] bool, [] char, [] String,
+    [T] Vec<T>,
+    [T, const N: usize] [T; N],
+    [T] HashSet<T>,
+    [T] HashMap<String, T>,
+    [T] BTreeMap<String, T>,
+    [T] Option<T>,
+}
+
+macro_rules! tuples {
+    ($($length:expr => ($($name:ident $index:tt),+))*) => {$(
+        impl<$($name: Into<Json>),+> From<($($name,)+)> for Json
+        {
+            fn from(tuple: ($($name,)+)) -> Json
+            {
+                Json::Array(vec![$(tuple.$index.into()),+])
+            }
+        }
+
+        impl<$($name),+> TryFrom<Json> for ($($name,)+)
+            where $($name: TryFrom<Json>, Error: From<$name::Error>),+
+        {
+            type Error = Error;
+
+            fn try_from(json: Json) -> Result<($($name,)+), Error>
+            {
+                let mut elements = match json {
+                    Json::Array(value) if value.len() == $length => value.into_iter(),
+                    Json::Array(value) => { return Err(Error::InvalidLength($length, value.len())); },
+                    _ => { return Err(Error::InvalidType("array", kind(&json))); },
+                };
+
+                Ok(($($name::try_from(elements.next().unwrap())?,)+))
+            }
+        }
+
+        impl<'a, $($name),+> TryFrom<&'a Json> for ($($name,)+)
+            where $($name: TryFrom<&'a Json>, Error: From<$name::Error>),+
+        {
+            type Error = Error;
+
+            fn try_from(json: &'a Json) -> Result<($($name,)+), Error>
+            {
+                let elements = match json {
+                    Json::Array(value) if value.len() == $length => value,
+                    Json::Array(value) => { return Err(Error::InvalidLength($length, value.len())); },
+                    _ => { return Err(Error::InvalidType("array", kind(json))); },
+                };
+
+                Ok(($($name::try_from(&elements[$index])?,)+))
+            }
+        }
+
+        wrappers!([$($name),+] ($($name,)+));
+    )*};
+}
+
+tuples! {
+    1 => (A 0)
+    2 => (A 0, B 1)
+    3 => (A 0, B 1, C 2)
+    4 => (A 0, B 1, C 2, D 3)
+    5 => (A 0, B 1, C 2, D 3, E 4)
+    6 => (A 0, B 1, C 2, D 3, E 4, F 5)
+    7 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    8 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+}
@@ -0,0 +1,554 @@
+use std::fmt;
+
+use crate::error::{ Error, Position };
+use crate::json::Json;
+use crate::parser::{ number, string_into };
+use crate::pointer::JsonPointer;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Scalar(Json),
+    Array(Vec<Child>),
+    Object(Vec<Child>),
+}
+
+// A value and the byte range it spans, brackets included for containers.
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    start: usize,
+    end:   usize,
+    kind:  Kind,
+}
+
+// An array element or object member.
+#[derive(Clone, Debug, PartialEq)]
+struct Child {
+    // Decoded key, with the byte offset following its closing quote.
+    key:   Option<(String, usize)>,
+    // Where the key starts, or the value for array elements.
+    start: usize,
+    value: Node,
+    // Offset of the comma separating it from the next child.
+    comma: Option<usize>,
+}
+
+/// A document keeping the exact text it was parsed from, whitespace and
+/// comments included, so it can be edited without reformatting it.
+///
+/// Comments may be written `// like this` or `/* like this */` anywhere
+/// whitespace is allowed. Paths are lists of object keys and array indexes,
+/// the latter written in decimal. Edits only rewrite the bytes of the value
+/// they touch and the separators around it, new values being written in
+/// compact form.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Document {
+    text: String,
+    root: Node,
+}
+
+impl Document {
+    /// Fails with `Error::Syntax` on malformed input.
+    pub fn parse(text: &str) -> Result<Document, Error>
+    {
+        let mut parser = Parser { text, index: 0 };
+
+        parser.trivia()?;
+        let root = parser.value()?;
+        parser.trivia()?;
+
+        if let Some(chr) = text[parser.index..].chars().next() {
+            return Err(parser.error(parser.index, Error::InvalidCharacter(chr.to_string())));
+        }
+
+        Ok(Document { text: String::from(text), root })
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        &self.text
+    }
+
+    pub fn to_json(&self) -> Json
+    {
+        self.root.to_json()
+    }
+
+    pub fn get(&self, path: &[&str]) -> Option<Json>
+    {
+        self.root.find(path).map(Node::to_json)
+    }
+
+    /// Replaces the value at `path`, which must exist.
+    pub fn set(&mut self, path: &[&str], value: &Json) -> Result<(), Error>
+    {
+        let node = self.root.find(path).ok_or_else(|| invalid_path(path))?;
+
+        self.splice(node.start, node.end, &value.to_string())
+    }
+
+    /// Adds a member at the end of the object at `path`, or replaces the
+    /// value of `key` if the object already has it.
+    pub fn insert(&mut self, path: &[&str], key: &str, value: &Json) -> Result<(), Error>
+    {
+        let node = self.root.find(path).ok_or_else(|| invalid_path(path))?;
+
+        let children = match node.kind {
+            Kind::Object(ref children) => children,
+            _ => { return Err(invalid_path(path)); },
+        };
+
+        if let Some(child) = children.iter().rev().find(|child| child.key_str() == Some(key)) {
+            let (start, end) = (child.value.start, child.value.end);
+
+            return self.splice(start, end, &value.to_string());
+        }
+
+        let member = format!("{}{}{}", Json::from(key), self.colon(children), value);
+
+        self.append(&node.clone(), &member)
+    }
+
+    /// Adds an element at the end of the array at `path`.
+    pub fn push(&mut self, path: &[&str], value: &Json) -> Result<(), Error>
+    {
+        let node = self.root.find(path).ok_or_else(|| invalid_path(path))?;
+
+        if !matches!(node.kind, Kind::Array(_)) {
+            return Err(invalid_path(path));
+        }
+
+        self.append(&node.clone(), &value.to_string())
+    }
+
+    /// Removes the element or member at `path` along with its separator,
+    /// and the whole line when nothing else is written on it.
+    pub fn remove(&mut self, path: &[&str]) -> Result<Json, Error>
+    {
+        let (last, parent) = match path.split_last() {
+            Some(split) => split,
+            None        => { return Err(invalid_path(path)); },
+        };
+
+        let node = self.root.find(parent).ok_or_else(|| invalid_path(path))?;
+
+        let children = match node.kind {
+            Kind::Array(ref children) | Kind::Object(ref children) => children,
+            _ => { return Err(invalid_path(path)); },
+        };
+
+        let index = position(node, children, last).ok_or_else(|| invalid_path(path))?;
+        let child = &children[index];
+        let value = child.value.to_json();
+        let end   = child.value.end;
+
+        if children.len() == 1 {
+            // Leave `[]` rather than a blank line.
+            let (open, close) = (node.start + 1, node.end - 1);
+
+            if self.text[open..child.start].trim().is_empty() && self.text[end..close].trim().is_empty() {
+                self.splice(open, close, "")?;
+            } else {
+                self.splice(child.start, end, "")?;
+            }
+
+        } else if let Some(comma) = child.comma {
+            match (self.line_start(child.start), self.line_end(comma + 1)) {
+                (Some(start), Some(end)) => { self.splice(start, end, "")?; },
+                _ => {
+                    let after = comma + 1 + self.spaces(comma + 1);
+                    self.splice(child.start, after, "")?;
+                },
+            }
+
+        } else {
+            // The last one, the comma to drop is the one before it.
+            let comma = children[index - 1].comma.unwrap();
+
+            match (self.line_start(child.start), self.line_end(end)) {
+                (Some(start), Some(end)) => {
+                    self.text.replace_range(start..end, "");
+                    self.splice(comma, comma + 1, "")?;
+                },
+                _ => { self.splice(comma, end, "")?; },
+            }
+        }
+
+        Ok(value)
+    }
+
+    // Adds `text` as the last child of a container.
+    fn append(&mut self, node: &Node, text: &str) -> Result<(), Error>
+    {
+        let (open, close) = (node.start + 1, node.end - 1);
+
+        let last = match node.kind {
+            Kind::Array(ref children) | Kind::Object(ref children) => children.last(),
+            Kind::Scalar(_) => None,
+        };
+
+        let last = match last {
+            Some(last) => last,
+            None => {
+                if self.text[open..close].trim().is_empty() {
+                    return self.splice(open, close, text);
+                }
+                return self.splice(open, open, text);
+            },
+        };
+
+        let end = last.value.end;
+
+        // Same separator as the one before the current last child.
+        let separator = String::from(&self.text[self.indent_start(last.start)..last.start]);
+
+        match self.line_end(end) {
+            // Keep a comment trailing the last child on its line.
+            Some(line_end) if separator.starts_with(['\n', '\r']) => {
+                let newline = if self.text[..line_end].ends_with("\r\n") { 2 } else { 1 };
+                let insert  = format!("{}{}", separator, text);
+
+                self.text.insert_str(line_end - newline, &insert);
+                self.splice(end, end, ",")
+            },
+            _ => {
+                self.splice(end, end, &format!(",{}{}", separator, text))
+            },
+        }
+    }
+
+    // What sits between keys and values in this object.
+    fn colon(&self, children: &[Child]) -> String
+    {
+        let colon = children.last()
+            .and_then(|child| child.key.as_ref().map(|(_, end)| &self.text[*end..child.value.start]));
+
+        match colon {
+            Some(colon) if !colon.contains('/') => String::from(colon),
+            _ => String::from(": "),
+        }
+    }
+
+    // Start of the line holding `index`, if only spaces come before it.
+    fn line_start(&self, index: usize) -> Option<usize>
+    {
+        let start = index - self.text[..index].chars().rev().take_while(|c| *c == ' ' || *c == '\t').count();
+
+        if self.text[..start].ends_with('\n') {
+            Some(start)
+        } else {
+            None
+        }
+    }
+
+    // Start of the spaces and tabs right before `index`, the line break
+    // before them included.
+    fn indent_start(&self, index: usize) -> usize
+    {
+        let mut start = index - self.text[..index].chars().rev().take_while(|c| *c == ' ' || *c == '\t').count();
+
+        if self.text[..start].ends_with('\n') {
+            start -= 1;
+        }
+        if self.text[..start].ends_with('\r') {
+            start -= 1;
+        }
+
+        start
+    }
+
+    // End of the line `index` is on, past its line break, if only spaces and
+    // a comment come after it.
+    fn line_end(&self, index: usize) -> Option<usize>
+    {
+        let mut index = index + self.spaces(index);
+        let rest      = &self.text[index..];
+
+        if rest.starts_with("//") {
+            index += rest.find('\n').unwrap_or(rest.len());
+        } else if rest.starts_with("/*") {
+            let end = rest.find("*/")? + 2;
+
+            if rest[..end].contains('\n') {
+                return None;
+            }
+            index += end;
+            index += self.spaces(index);
+        }
+
+        let rest = &self.text[index..];
+
+        if rest.starts_with("\r\n") {
+            Some(index + 2)
+        } else if rest.starts_with('\n') {
+            Some(index + 1)
+        } else {
+            None
+        }
+    }
+
+    fn spaces(&self, index: usize) -> usize
+    {
+        self.text[index..].chars().take_while(|c| *c == ' ' || *c == '\t').count()
+    }
+
+    // Replaces a range of the text and parses the result again.
+    fn splice(&mut self, start: usize, end: usize, text: &str) -> Result<(), Error>
+    {
+        let mut edited = self.text.clone();
+        edited.replace_range(start..end, text);
+
+        *self = Document::parse(&edited)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Document
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        f.write_str(&self.text)
+    }
+}
+
+impl Node {
+    fn to_json(&self) -> Json
+    {
+        match self.kind {
+            Kind::Scalar(ref value)      => value.clone(),
+            Kind::Array(ref children)  => Json::Array(children.iter().map(|c| c.value.to_json()).collect()),
+            Kind::Object(ref children) => Json::Object(
+                children.iter()
+                    .map(|c| (String::from(c.key_str().unwrap()), c.value.to_json()))
+                    .collect()
+            ),
+        }
+    }
+
+    fn find(&self, path: &[&str]) -> Option<&Node>
+    {
+        let (first, rest) = match path.split_first() {
+            Some(split) => split,
+            None        => { return Some(self); },
+        };
+
+        let children = match self.kind {
+            Kind::Array(ref children) | Kind::Object(ref children) => children,
+            Kind::Scalar(_) => { return None; },
+        };
+
+        children[position(self, children, first)?].value.find(rest)
+    }
+}
+
+impl Child {
+    fn key_str(&self) -> Option<&str>
+    {
+        self.key.as_ref().map(|(key, _)| key.as_str())
+    }
+}
+
+// Index of the child `segment` refers to. With duplicate keys, the last one
+// wins as it does when parsing into a `Json`.
+fn position(node: &Node, children: &[Child], segment: &str) -> Option<usize>
+{
+    match node.kind {
+        Kind::Object(_) => children.iter().rposition(|c| c.key_str() == Some(segment)),
+        _ if segment.bytes().all(|b| b.is_ascii_digit()) => {
+            segment.parse().ok().filter(|index| *index < children.len())
+        },
+        _ => None,
+    }
+}
+
+fn invalid_path(path: &[&str]) -> Error
+{
+    let path: JsonPointer = path.iter().cloned().collect();
+
+    Error::InvalidPath(path.to_string())
+}
+
+struct Parser<'a> {
+    text:  &'a str,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, offset: usize, error: Error) -> Error
+    {
+        let before = &self.text[..offset];
+        let line   = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |i| i + 1)..].chars().count() + 1;
+
+        Error::Syntax(Position { line, column, offset }, Box::new(error))
+    }
+
+    fn peek(&self) -> Option<char>
+    {
+        self.text[self.index..].chars().next()
+    }
+
+    fn unexpected(&self) -> Error
+    {
+        match self.peek() {
+            Some(chr) => self.error(self.index, Error::InvalidCharacter(chr.to_string())),
+            None      => self.error(self.index, Error::UnexpectedEof),
+        }
+    }
+
+    // Skips whitespace and comments.
+    fn trivia(&mut self) -> Result<(), Error>
+    {
+        loop {
+            let rest = &self.text[self.index..];
+
+            if rest.starts_with([' ', '\t', '\r', '\n']) {
+                self.index += 1;
+            } else if rest.starts_with("//") {
+                self.index += rest.find('\n').unwrap_or(rest.len());
+            } else if rest.starts_with("/*") {
+                match rest.find("*/") {
+                    Some(end) => { self.index += end + 2; },
+                    None      => { return Err(self.error(self.text.len(), Error::UnexpectedEof)); },
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node, Error>
+    {
+        let start = self.index;
+
+        let kind = match self.peek() {
+            Some('[') => Kind::Array(self.children(']')?),
+            Some('{') => Kind::Object(self.children('}')?),
+            Some('"') => Kind::Scalar(Json::String(self.string()?)),
+            Some('0'..='9') | Some('-') => Kind::Scalar(self.number()?),
+            Some('t') | Some('f') | Some('n') => Kind::Scalar(self.literal()?),
+            _ => { return Err(self.unexpected()); },
+        };
+
+        Ok(Node { start, end: self.index, kind })
+    }
+
+    fn children(&mut self, close: char) -> Result<Vec<Child>, Error>
+    {
+        let mut children = vec![];
+
+        self.index += 1;
+        self.trivia()?;
+
+        if self.peek() == Some(close) {
+            self.index += 1;
+            return Ok(children);
+        }
+
+        loop {
+            let start = self.index;
+            let mut key = None;
+
+            if close == '}' {
+                if self.peek() != Some('"') {
+                    return Err(self.unexpected());
+                }
+
+                key = Some((self.string()?, self.index));
+                self.trivia()?;
+
+                if self.peek() != Some(':') {
+                    return Err(self.unexpected());
+                }
+
+                self.index += 1;
+                self.trivia()?;
+            }
+
+            let value = self.value()?;
+            self.trivia()?;
+
+            let mut child = Child { key, start, value, comma: None };
+
+            match self.peek() {
+                Some(',') => {
+                    child.comma = Some(self.index);
+                    children.push(child);
+
+                    self.index += 1;
+                    self.trivia()?;
+                },
+                Some(chr) if chr == close => {
+                    children.push(child);
+
+                    self.index += 1;
+                    return Ok(children);
+                },
+                _ => { return Err(self.unexpected()); },
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Error>
+    {
+        let start = self.index;
+        let bytes = self.text.as_bytes();
+        let mut index = start + 1;
+
+        loop {
+            match bytes.get(index) {
+                Some(b'"')  => { index += 1; break; },
+                Some(b'\\') => { index += 2; },
+                Some(_)     => { index += 1; },
+                None        => { return Err(self.error(self.text.len(), Error::UnexpectedEof)); },
+            }
+        }
+
+        let mut slice    = self.text[start..index].chars();
+        let mut peekable = (&mut slice).peekable();
+        let mut decoded  = String::new();
+
+        string_into(&mut peekable, &mut decoded).map_err(|error| self.error(start, error))?;
+
+        self.index = index;
+        Ok(decoded)
+    }
+
+    fn number(&mut self) -> Result<Json, Error>
+    {
+        let start = self.index;
+        let end   = start + self.text[start..].bytes()
+            .take_while(|b| matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'))
+            .count();
+
+        self.index = end;
+
+        let mut slice    = self.text[start..end].chars();
+        let mut peekable = (&mut slice).peekable();
+
+        let result = match number(&mut peekable) {
+            // Digits were missing at the end of the token.
+            Err(Error::UnexpectedEof) => { return Err(self.unexpected()); },
+            result => result,
+        };
+
+        match peekable.next() {
+            Some(chr) => Err(self.error(start, Error::InvalidCharacter(chr.to_string()))),
+            None      => result.map_err(|error| self.error(start, error)),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Json, Error>
+    {
+        let start = self.index;
+        let word: String = self.text[start..].chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+
+        self.index += word.len();
+
+        match word.as_str() {
+            "true"  => Ok(Json::Boolean(true)),
+            "false" => Ok(Json::Boolean(false)),
+            "null"  => Ok(Json::Null),
+            _ => Err(self.error(start, Error::InvalidCharacter(word))),
+        }
+    }
+}
@@ -1,6 +1,59 @@
+use std::fmt;
+use std::io;
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     UnexpectedEof,
     InvalidCharacter(String),
+    NonFiniteNumber(String),
+    Io(io::ErrorKind, String),
+    InvalidCall(String),
+    /// A conversion expected one kind of value and found another, as in
+    /// `InvalidType("string", "number")`.
+    InvalidType(&'static str, &'static str),
+    /// A number the target type of a conversion can't hold exactly, as in
+    /// `OutOfRange("-1", "u64")`.
+    OutOfRange(String, &'static str),
+    /// An array converted into a fixed size type had the wrong number of
+    /// elements: expected and found.
+    InvalidLength(usize, usize),
+    /// A path leading nowhere, written as a JSON Pointer.
+    InvalidPath(String),
+    /// A JSON Patch document that isn't one, as in `InvalidPatch("missing
+    /// \"path\"")`.
+    InvalidPatch(String),
+    /// A `test` operation of a JSON Patch found another value at this path.
+    TestFailed(String),
+    /// A worker thread of `Ndjson` panicked while parsing the chunk starting
+    /// at the line reported with it.
+    Panicked(String),
+    /// An error found while reading a stream, and where it happened.
+    Syntax(Position, Box<Error>),
+}
+
+/// A place in some input text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    /// Starting from 1.
+    pub line:   usize,
+    /// Starting from 1, counting characters rather than bytes.
+    pub column: usize,
+    /// Bytes before this position.
+    pub offset: usize,
 }
 
+impl From<io::Error> for Error
+{
+    fn from(error: io::Error) -> Error
+    {
+        Error::Io(error.kind(), error.to_string())
+    }
+}
+
+impl From<fmt::Error> for Error
+{
+    fn from(error: fmt::Error) -> Error
+    {
+        Error::Io(io::ErrorKind::Other, error.to_string())
+    }
+}
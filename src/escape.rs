@@ -0,0 +1,49 @@
+use std::fmt::{ self, Write };
+
+use crate::format::Format;
+
+// Writes `value` as a quoted JSON string. Quotation marks, backslashes and
+// control characters are always escaped, the rest depends on `format`.
+pub fn escape_into<W: Write>(writer: &mut W, value: &str, format: &Format) -> fmt::Result
+{
+    writer.write_char('"')?;
+
+    // Start of the pending run of characters that don't need escaping.
+    let mut start = 0;
+
+    for (index, chr) in value.char_indices() {
+        let escaped = match chr {
+            '"'      => "\\\"",
+            '\\'     => "\\\\",
+            '\u{08}' => "\\b",
+            '\u{0C}' => "\\f",
+            '\n'     => "\\n",
+            '\r'     => "\\r",
+            '\t'     => "\\t",
+
+            '\u{00}'..='\u{1F}' => "",
+            '<' | '>' | '&' if format.html_safe => "",
+            '\u{2028}' | '\u{2029}' if format.escape_separators => "",
+            _ if format.ascii_only && !chr.is_ascii() => "",
+
+            _ => { continue; },
+        };
+
+        writer.write_str(&value[start..index])?;
+        start = index + chr.len_utf8();
+
+        if !escaped.is_empty() {
+            writer.write_str(escaped)?;
+            continue;
+        }
+
+        let mut units = [0; 2];
+
+        for unit in chr.encode_utf16(&mut units) {
+            write!(writer, "\\u{:04x}", unit)?;
+        }
+    }
+
+    writer.write_str(&value[start..])?;
+    writer.write_char('"')
+}
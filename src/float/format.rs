@@ -0,0 +1,149 @@
+// Shortest decimal digits that round-trip to `value`, along with the position
+// of the decimal point: `value == 0.DIGITS * 10^point`. `value` must be
+// finite and non-zero, its sign is ignored.
+pub fn shortest(value: f64) -> (String, i32)
+{
+    let value = value.abs();
+
+    // The standard library already picks the shortest round-tripping digits,
+    // we only need them split from the exponent.
+    let (digits, point) = split(&format!("{:e}", value));
+
+    // When the value lies exactly halfway between two candidates of that
+    // length, ECMAScript picks the even one while the standard library may
+    // not. Both candidates can only round-trip from 16 digits onwards.
+    if digits.len() >= 16 {
+        // Rust prints the exact expansion when given enough precision.
+        let (exact, exact_point) = split(&format!("{:.800e}", value));
+        let exact = exact.trim_end_matches('0');
+
+        if exact.len() == digits.len() + 1 && exact.ends_with('5') {
+            let (even, even_point) = even_neighbour(&exact[..digits.len()], exact_point);
+
+            if even != digits && format!("0.{}e{}", even, even_point).parse::<f64>() == Ok(value) {
+                return (even, even_point);
+            }
+        }
+    }
+
+    (digits, point)
+}
+
+// Splits the output of `{:e}` into its digits and decimal point position.
+fn split(formatted: &str) -> (String, i32)
+{
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+
+    let digits: String = mantissa.chars().filter(|chr| *chr != '.').collect();
+    let exponent: i32  = exponent[1..].parse().unwrap();
+
+    (digits, exponent + 1)
+}
+
+// Returns `lower` if its last digit is even, otherwise the next number with
+// as many digits, which may carry into a new leading digit.
+fn even_neighbour(lower: &str, point: i32) -> (String, i32)
+{
+    if lower.ends_with(['0', '2', '4', '6', '8']) {
+        return (String::from(lower), point);
+    }
+
+    let mut digits = lower.as_bytes().to_vec();
+    let mut index  = digits.len();
+
+    loop {
+        // All nines, 99..9 + 1 = 100..0.
+        if index == 0 {
+            return (String::from("1"), point + 1);
+        }
+
+        index -= 1;
+
+        if digits[index] == b'9' {
+            digits[index] = b'0';
+        } else {
+            digits[index] += 1;
+            break;
+        }
+    }
+
+    let digits = String::from_utf8(digits).unwrap();
+    let trimmed = digits.trim_end_matches('0');
+
+    (String::from(trimmed), point)
+}
+
+// Formats a float the way ECMAScript's Number.prototype.toString does, but
+// always leaving a fraction or an exponent so it reads back as a float.
+// Non-finite values are written as JSON5 literals.
+pub fn format(value: f64) -> String
+{
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+
+    if value.is_infinite() {
+        return String::from(if value > 0.0 { "Infinity" } else { "-Infinity" });
+    }
+
+    if value == 0.0 {
+        return String::from(if value.is_sign_negative() { "-0.0" } else { "0.0" });
+    }
+
+    let mut string = ecmascript(value);
+
+    if !string.contains(['.', 'e']) {
+        string.push_str(".0");
+    }
+
+    string
+}
+
+// ECMAScript's Number.prototype.toString for finite values, as required by
+// RFC 8785. Negative zero is written as 0.
+pub fn ecmascript(value: f64) -> String
+{
+    if value == 0.0 {
+        return String::from("0");
+    }
+
+    let mut string = String::new();
+
+    if value.is_sign_negative() {
+        string.push('-');
+    }
+
+    let (digits, point) = shortest(value);
+    let length          = digits.len() as i32;
+
+    if length <= point && point <= 21 {
+        string.push_str(&digits);
+        for _ in length..point {
+            string.push('0');
+        }
+
+    } else if 0 < point && point <= 21 {
+        string.push_str(&digits[..point as usize]);
+        string.push('.');
+        string.push_str(&digits[point as usize..]);
+
+    } else if -6 < point && point <= 0 {
+        string.push_str("0.");
+        for _ in point..0 {
+            string.push('0');
+        }
+        string.push_str(&digits);
+
+    } else {
+        string.push_str(&digits[..1]);
+        if length > 1 {
+            string.push('.');
+            string.push_str(&digits[1..]);
+        }
+        string.push('e');
+        string.push(if point > 0 { '+' } else { '-' });
+        string.push_str(&(point - 1).abs().to_string());
+    }
+
+    string
+}
@@ -0,0 +1,117 @@
+use crate::float::table::{ LARGEST_POWER_OF_FIVE, POWER_OF_FIVE_128, SMALLEST_POWER_OF_FIVE };
+
+const MANTISSA_BITS:          i32 = 52;
+const MINIMUM_EXPONENT:       i32 = -1023;
+const INFINITE_POWER:         i32 = 0x7FF;
+const MIN_EXPONENT_ROUND_EVEN: i64 = -4;
+const MAX_EXPONENT_ROUND_EVEN: i64 = 23;
+
+// Converts `w * 10^q` into the bits of the closest f64, or returns None when
+// the 128-bit approximation isn't enough to decide how to round.
+//
+// Daniel Lemire, "Number Parsing at a Gigabyte per Second",
+// Software: Practice and Experience 51 (8), 2021.
+pub fn compute_float(q: i64, mut w: u64) -> Option<u64>
+{
+    if w == 0 || q < SMALLEST_POWER_OF_FIVE {
+        return Some(0);
+    }
+
+    if q > LARGEST_POWER_OF_FIVE {
+        return Some((INFINITE_POWER as u64) << MANTISSA_BITS);
+    }
+
+    // Normalize, so the most significant bit is set.
+    let lz = w.leading_zeros() as i32;
+    w <<= lz;
+
+    let (lo, hi) = product_approx(q, w, MANTISSA_BITS as u32 + 3);
+
+    // The product may be off by one, which matters only out of the range where
+    // 5^q is exactly representable in 128 bits.
+    if lo == 0xFFFF_FFFF_FFFF_FFFF && !(-27..=55).contains(&q) {
+        return None;
+    }
+
+    let upperbit     = (hi >> 63) as i32;
+    let shift        = upperbit + 64 - MANTISSA_BITS - 3;
+    let mut mantissa = hi >> shift;
+    let mut power2   = power(q as i32) + upperbit - lz - MINIMUM_EXPONENT;
+
+    if power2 <= 0 {
+        // More than 64 bits below the minimum exponent, it's zero.
+        if -power2 + 1 >= 64 {
+            return Some(0);
+        }
+
+        // Subnormal.
+        mantissa >>= -power2 + 1;
+        mantissa  += mantissa & 1;
+        mantissa >>= 1;
+
+        // Rounding up may have turned it into the smallest normal number.
+        power2 = if mantissa < (1 << MANTISSA_BITS) { 0 } else { 1 };
+
+        return Some(((power2 as u64) << MANTISSA_BITS) | (mantissa & !(1 << MANTISSA_BITS)));
+    }
+
+    // Exactly halfway between two floats, round to even instead of up.
+    if lo <= 1
+        && (MIN_EXPONENT_ROUND_EVEN..=MAX_EXPONENT_ROUND_EVEN).contains(&q)
+        && mantissa & 3 == 1
+        && (mantissa << shift) == hi
+    {
+        mantissa &= !1;
+    }
+
+    mantissa  += mantissa & 1;
+    mantissa >>= 1;
+
+    // Rounding overflowed into the next power of two.
+    if mantissa >= (2 << MANTISSA_BITS) {
+        mantissa = 1 << MANTISSA_BITS;
+        power2  += 1;
+    }
+
+    mantissa &= !(1 << MANTISSA_BITS);
+
+    if power2 >= INFINITE_POWER {
+        return Some((INFINITE_POWER as u64) << MANTISSA_BITS);
+    }
+
+    Some(((power2 as u64) << MANTISSA_BITS) | mantissa)
+}
+
+// floor(log2(10^q)) + 63, valid for q in [-1233, 1233].
+fn power(q: i32) -> i32
+{
+    (q.wrapping_mul(152_170 + 65536) >> 16) + 63
+}
+
+fn full_multiplication(a: u64, b: u64) -> (u64, u64)
+{
+    let r = (a as u128) * (b as u128);
+    (r as u64, (r >> 64) as u64)
+}
+
+// Returns the (low, high) words of the truncated product of `w` and 5^q,
+// precise enough for the `precision` most significant bits.
+fn product_approx(q: i64, w: u64, precision: u32) -> (u64, u64)
+{
+    let mask = 0xFFFF_FFFF_FFFF_FFFF_u64 >> precision;
+
+    let (hi5, lo5) = POWER_OF_FIVE_128[(q - SMALLEST_POWER_OF_FIVE) as usize];
+    let (mut first_lo, mut first_hi) = full_multiplication(w, hi5);
+
+    if first_hi & mask == mask {
+        let (_, second_hi) = full_multiplication(w, lo5);
+
+        first_lo = first_lo.wrapping_add(second_hi);
+
+        if second_hi > first_lo {
+            first_hi += 1;
+        }
+    }
+
+    (first_lo, first_hi)
+}
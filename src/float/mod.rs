@@ -0,0 +1,101 @@
+mod format;
+mod lemire;
+mod table;
+
+pub use self::format::{ ecmascript, format };
+
+const POWERS_OF_TEN: [f64; 23] = [
+    1e0,  1e1,  1e2,  1e3,  1e4,  1e5,  1e6,  1e7,  1e8,  1e9,  1e10, 1e11,
+    1e12, 1e13, 1e14, 1e15, 1e16, 1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+// Largest mantissa that can be represented exactly, 2^53.
+const MAX_EXACT_MANTISSA: u64 = 2 << 52;
+
+// Converts a token already validated by the number parser into the closest
+// f64. Falls back to the standard library whenever the fast paths can't
+// guarantee a correctly rounded result.
+pub fn parse(token: &str) -> f64
+{
+    let bytes    = token.as_bytes();
+    let negative = bytes.first() == Some(&b'-');
+
+    let mut mantissa: u64 = 0;
+    let mut exponent: i64 = 0;
+    let mut digits        = 0;
+    let mut index         = if negative { 1 } else { 0 };
+
+    // Integer part.
+    while index < bytes.len() && bytes[index].is_ascii_digit() {
+        if digits > 0 || bytes[index] != b'0' {
+            mantissa = mantissa.wrapping_mul(10).wrapping_add(u64::from(bytes[index] - b'0'));
+            digits  += 1;
+        }
+        index += 1;
+    }
+
+    // Fraction part.
+    if index < bytes.len() && bytes[index] == b'.' {
+        index += 1;
+
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            if digits > 0 || bytes[index] != b'0' {
+                mantissa = mantissa.wrapping_mul(10).wrapping_add(u64::from(bytes[index] - b'0'));
+                digits  += 1;
+            }
+            exponent -= 1;
+            index    += 1;
+        }
+    }
+
+    // Exponent part.
+    if index < bytes.len() && (bytes[index] == b'e' || bytes[index] == b'E') {
+        index += 1;
+
+        let negative_exp = bytes.get(index) == Some(&b'-');
+        if bytes.get(index) == Some(&b'-') || bytes.get(index) == Some(&b'+') {
+            index += 1;
+        }
+
+        let mut value: i64 = 0;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            // Saturate well beyond the range where every value is 0 or infinity.
+            if value < 0x1000_0000 {
+                value = value * 10 + i64::from(bytes[index] - b'0');
+            }
+            index += 1;
+        }
+
+        exponent += if negative_exp { -value } else { value };
+    }
+
+    // Too many significant digits for the mantissa to be exact.
+    if digits > 19 {
+        return fallback(token);
+    }
+
+    let value = if mantissa == 0 {
+        0.0
+
+    } else if mantissa <= MAX_EXACT_MANTISSA && (-22..=22).contains(&exponent) {
+        // Both operands are exact, so is the single rounding IEEE 754 performs.
+        if exponent >= 0 {
+            mantissa as f64 * POWERS_OF_TEN[exponent as usize]
+        } else {
+            mantissa as f64 / POWERS_OF_TEN[-exponent as usize]
+        }
+
+    } else {
+        match lemire::compute_float(exponent, mantissa) {
+            Some(bits) => f64::from_bits(bits),
+            None       => { return fallback(token); },
+        }
+    };
+
+    if negative { -value } else { value }
+}
+
+fn fallback(token: &str) -> f64
+{
+    token.parse::<f64>().unwrap_or(f64::NAN)
+}
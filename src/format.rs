@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::color::Palette;
+
+/// What to write for NaN and infinite floats, which JSON can't represent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonFinite {
+    /// Fail with `Error::NonFiniteNumber`.
+    Error,
+    /// Write `null`, like JavaScript's `JSON.stringify` does.
+    Null,
+    /// Write `"NaN"`, `"Infinity"` or `"-Infinity"` as strings.
+    String,
+    /// Write the JSON5 literals `NaN`, `Infinity` and `-Infinity`.
+    Literal,
+}
+
+type Comparator = dyn Fn(&str, &str) -> Ordering + Send + Sync;
+
+/// Order in which object entries are written.
+#[derive(Clone)]
+pub enum KeyOrder {
+    /// Whatever order the underlying `HashMap` iterates in.
+    Unsorted,
+    /// Byte-wise comparison of the keys.
+    Lexicographic,
+    /// Like lexicographic, but runs of digits compare by their numeric
+    /// value, so `item2` comes before `item10`.
+    Natural,
+    /// Any total order over the keys, see `KeyOrder::custom`.
+    Custom(Arc<Comparator>),
+}
+
+/// Options controlling how a `Json` value is serialized.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Format {
+    pub non_finite: NonFinite,
+
+    /// Escape every non-ASCII character as `\uXXXX`, using surrogate pairs
+    /// outside the Basic Multilingual Plane.
+    pub ascii_only: bool,
+    /// Escape `<`, `>` and `&`, so the output can be embedded in HTML.
+    pub html_safe: bool,
+    /// Escape U+2028 and U+2029, which end lines in JavaScript source code.
+    pub escape_separators: bool,
+
+    /// String repeated once per nesting level. Output is compact when `None`.
+    pub indent: Option<String>,
+    /// Line break written after each element when indenting.
+    pub newline: String,
+    /// Line up the scalar values of an object on the same column.
+    pub align_values: bool,
+    pub space_after_colon: bool,
+    /// Arrays holding up to this many scalars are kept on a single line.
+    /// Ignored when `max_width` is set.
+    pub inline_arrays: usize,
+    /// When indenting, keep any array or object on a single line if it fits
+    /// within this many characters, indentation included, and break it over
+    /// several lines otherwise.
+    pub max_width: Option<usize>,
+
+    /// Applied to objects at every level.
+    pub sort_keys: KeyOrder,
+
+    /// Wraps each token in ANSI escape sequences for terminals, see
+    /// `Palette::auto`.
+    pub colors: Option<Palette>,
+}
+
+impl Format {
+    pub fn new() -> Format
+    {
+        Format {
+            non_finite:        NonFinite::Null,
+            ascii_only:        false,
+            html_safe:         false,
+            escape_separators: false,
+            indent:            None,
+            newline:           String::from("\n"),
+            align_values:      false,
+            space_after_colon: false,
+            inline_arrays:     0,
+            max_width:         None,
+            sort_keys:         KeyOrder::Unsorted,
+            colors:            None,
+        }
+    }
+
+    /// Two-space indentation with aligned values, as shown in the README.
+    pub fn pretty() -> Format
+    {
+        Format {
+            indent:            Some(String::from("  ")),
+            align_values:      true,
+            space_after_colon: true,
+            inline_arrays:     8,
+            ..Format::new()
+        }
+    }
+}
+
+impl Default for Format
+{
+    fn default() -> Format
+    {
+        Format::new()
+    }
+}
+
+// Custom orders are only equal when they share the same comparator.
+impl PartialEq for KeyOrder
+{
+    fn eq(&self, other: &KeyOrder) -> bool
+    {
+        match (self, other) {
+            (KeyOrder::Custom(a), KeyOrder::Custom(b)) => {
+                Arc::as_ptr(a) as *const () == Arc::as_ptr(b) as *const ()
+            },
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl fmt::Debug for KeyOrder
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            KeyOrder::Unsorted      => formatter.write_str("Unsorted"),
+            KeyOrder::Lexicographic => formatter.write_str("Lexicographic"),
+            KeyOrder::Natural       => formatter.write_str("Natural"),
+            KeyOrder::Custom(_)     => formatter.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl KeyOrder {
+    /// Orders keys with `compare`, which may capture whatever it needs, like
+    /// a list of keys to write first.
+    pub fn custom<F>(compare: F) -> KeyOrder
+        where F: Fn(&str, &str) -> Ordering + Send + Sync + 'static
+    {
+        KeyOrder::Custom(Arc::new(compare))
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Ordering
+    {
+        match self {
+            KeyOrder::Unsorted      => Ordering::Equal,
+            KeyOrder::Lexicographic => a.cmp(b),
+            KeyOrder::Natural       => natural(a, b),
+            KeyOrder::Custom(cmp)   => cmp(a, b),
+        }
+    }
+}
+
+fn natural(a: &str, b: &str) -> Ordering
+{
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+
+    loop {
+        let (x, y) = match (a.peek(), b.peek()) {
+            (None, None)       => { return Ordering::Equal; },
+            (None, _)          => { return Ordering::Less; },
+            (_, None)          => { return Ordering::Greater; },
+            (Some(x), Some(y)) => (*x, *y),
+        };
+
+        if !x.is_ascii_digit() || !y.is_ascii_digit() {
+            match x.cmp(&y) {
+                Ordering::Equal => { a.next(); b.next(); },
+                other           => { return other; },
+            }
+
+            continue;
+        }
+
+        let x = digits(&mut a);
+        let y = digits(&mut b);
+
+        let (x_value, y_value) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+
+        // Longer numbers are bigger, same length ones compare digit by digit.
+        // Equal numbers with more leading zeros go last.
+        let order = x_value.len().cmp(&y_value.len())
+            .then_with(|| x_value.cmp(y_value))
+            .then_with(|| x.len().cmp(&y.len()));
+
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+}
+
+fn digits<I: Iterator<Item = char>>(chars: &mut std::iter::Peekable<I>) -> String
+{
+    let mut digits = String::new();
+
+    while let Some(chr) = chars.peek() {
+        if !chr.is_ascii_digit() {
+            break;
+        }
+
+        digits.push(*chr);
+        chars.next();
+    }
+
+    digits
+}
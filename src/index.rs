@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::ops;
+
+use crate::json::Json;
+
+// Returned when reading a missing key or element.
+static NULL: Json = Json::Null;
+
+/// Anything a `Json` can be indexed with: `usize` for arrays, string types
+/// for objects.
+pub trait JsonIndex {
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json>;
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json>;
+
+    // Creates whatever is missing on the way, see `IndexMut`.
+    fn index_or_insert<'a>(&self, json: &'a mut Json) -> &'a mut Json;
+}
+
+impl JsonIndex for usize
+{
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json>
+    {
+        match json {
+            Json::Array(ref value) => value.get(*self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json>
+    {
+        match json {
+            Json::Array(ref mut value) => value.get_mut(*self),
+            _ => None,
+        }
+    }
+
+    fn index_or_insert<'a>(&self, json: &'a mut Json) -> &'a mut Json
+    {
+        if *json == Json::Null {
+            *json = Json::Array(vec![]);
+        }
+
+        match json {
+            Json::Array(ref mut value) => {
+                // Arrays only grow one element at a time, so a stray index
+                // can't allocate an arbitrary amount of memory.
+                if *self > value.len() {
+                    panic!("Index {} out of bounds for an array of length {}", self, value.len());
+                }
+
+                if *self == value.len() {
+                    value.push(Json::Null);
+                }
+
+                &mut value[*self]
+            },
+            _ => { panic!("Cannot index {:?} with {}", json, self); },
+        }
+    }
+}
+
+impl JsonIndex for str
+{
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json>
+    {
+        match json {
+            Json::Object(ref value) => value.get(self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json>
+    {
+        match json {
+            Json::Object(ref mut value) => value.get_mut(self),
+            _ => None,
+        }
+    }
+
+    fn index_or_insert<'a>(&self, json: &'a mut Json) -> &'a mut Json
+    {
+        if *json == Json::Null {
+            *json = Json::Object(HashMap::new());
+        }
+
+        match json {
+            Json::Object(ref mut value) => value.entry(String::from(self)).or_insert(Json::Null),
+            _ => { panic!("Cannot index {:?} with {:?}", json, self); },
+        }
+    }
+}
+
+impl JsonIndex for String
+{
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json>
+    {
+        self.as_str().index_into(json)
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json>
+    {
+        self.as_str().index_into_mut(json)
+    }
+
+    fn index_or_insert<'a>(&self, json: &'a mut Json) -> &'a mut Json
+    {
+        self.as_str().index_or_insert(json)
+    }
+}
+
+impl<T: JsonIndex + ?Sized> JsonIndex for &T
+{
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json>
+    {
+        (**self).index_into(json)
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json>
+    {
+        (**self).index_into_mut(json)
+    }
+
+    fn index_or_insert<'a>(&self, json: &'a mut Json) -> &'a mut Json
+    {
+        (**self).index_or_insert(json)
+    }
+}
+
+impl Json {
+    /// Looks up an array element or object member, `None` when missing or
+    /// when `self` isn't the right kind of container.
+    pub fn get<I: JsonIndex>(&self, index: I) -> Option<&Json>
+    {
+        index.index_into(self)
+    }
+
+    pub fn get_mut<I: JsonIndex>(&mut self, index: I) -> Option<&mut Json>
+    {
+        index.index_into_mut(self)
+    }
+}
+
+/// Reading a missing element or member, or indexing something which isn't a
+/// container, gives `Json::Null`, so lookups can be chained.
+impl<I: JsonIndex> ops::Index<I> for Json
+{
+    type Output = Json;
+
+    fn index(&self, index: I) -> &Json
+    {
+        index.index_into(self).unwrap_or(&NULL)
+    }
+}
+
+/// Writing turns `Json::Null` into an empty array or object as needed, adds
+/// missing members and appends to an array when the index is its length.
+/// Panics on an index past that, or when indexing any other kind of value.
+impl<I: JsonIndex> ops::IndexMut<I> for Json
+{
+    fn index_mut(&mut self, index: I) -> &mut Json
+    {
+        index.index_or_insert(self)
+    }
+}
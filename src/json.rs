@@ -1,11 +1,23 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::{ Hash, Hasher };
+use std::io;
+use std::mem;
 
-
+use crate::canonical::canonicalize;
 use crate::error::Error;
+use crate::format::Format;
 use crate::parser::node;
 use crate::number::Number;
-
-#[derive(Clone, Debug, PartialEq)]
+use crate::serializer::{ serialize, IoAdapter };
+
+/// Values of different kinds order as `Null < Boolean < Number < String <
+/// Array < Object`. Arrays compare element by element, objects compare their
+/// entries sorted by key, and numbers follow `Number`, where NaN equals itself
+/// and sorts after every other number. Hashing is consistent with this, so
+/// objects hash the same whatever the order of their keys in the map.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Json {
     Object(HashMap<String, Json>),
     Array(Vec<Json>),
@@ -24,252 +36,270 @@ impl Json {
         node(&mut peekable)
     }
 
-    pub fn to_string(&self) -> String
+    pub fn to_string_with(&self, format: &Format) -> Result<String, Error>
     {
-        let mut string: String = String::new();
+        let mut string = String::new();
 
-        match self {
-            Json::Null => {
-                string.push_str("null");
-            },
-            Json::Boolean(value) => {
-                string.push_str(
-                    if *value { "true"  }
-                    else      { "false" }
-                );
-            },
-            Json::Number(ref value) => {
-                string.push_str(value.to_string().as_str());
-            },
-            Json::String(ref value) => {
-                string.push('"');
-                for chr in value.chars() {
-                    if chr == '"' {
-                        string.push('\\');
-                    }
-                    string.push(chr);
-                }
-                string.push('"');
-            },
-            Json::Array(ref value) => {
-                let mut first = true;
-
-                string.push('[');
-                for elem in value {
-                    if !first {
-                        string.push(',');
-                    }
-                    string.push_str(elem.to_string().as_str());
-                    first = false;
-                }
-                string.push(']');
-            },
-            Json::Object(ref value) => {
-                let mut first = true;
-
-                string.push('{');
-                for (k, v) in value {
-                    if !first {
-                        string.push(',');
-                    }
-                    string.push('"');
-                    for chr in k.chars() {
-                        if chr == '"' {
-                            string.push('\\');
-                        }
-                        string.push(chr);
-                    }
-                    string.push('"');
-                    string.push(':');
-                    string.push_str(v.to_string().as_str());
-                    first = false;
-                }
-                string.push('}');
-            },
-        }
+        serialize(&mut string, self, format, 0)?;
 
-        string
+        Ok(string)
     }
-}
 
-impl From<HashMap<String, Json>> for Json
-{
-    fn from(map: HashMap<String, Json>) -> Json
+    /// Indented output, see `Format::pretty`.
+    pub fn to_pretty_string(&self) -> String
     {
-        Json::Object(map)
+        self.to_string_with(&Format::pretty()).unwrap()
     }
-}
 
-impl From<Vec<Json>> for Json
-{
-    fn from(vector: Vec<Json>) -> Json
+    /// Streams the serialized value into `writer` in a single pass. Writers
+    /// performing a system call per write should be wrapped in a `BufWriter`.
+    pub fn write_to<W: io::Write>(&self, writer: &mut W, format: &Format) -> Result<(), Error>
     {
-        Json::Array(vector)
+        let mut adapter = IoAdapter::new(writer);
+        let result      = serialize(&mut adapter, self, format, 0);
+
+        adapter.result(result)
     }
-}
 
-impl From<String> for Json
-{
-    fn from(string: String) -> Json
+    pub fn format_to<W: fmt::Write>(&self, writer: &mut W, format: &Format) -> Result<(), Error>
     {
-        Json::String(string)
+        serialize(writer, self, format, 0)
     }
-}
 
-impl<'a> From<&'a str> for Json
-{
-    fn from(string: &'a str) -> Json
+    /// Canonical form defined by RFC 8785, suitable for hashing and signing.
+    /// Fails on NaN and infinities.
+    pub fn to_canonical_string(&self) -> Result<String, Error>
     {
-        Json::String(String::from(string))
+        let mut string = String::new();
+
+        canonicalize(&mut string, self)?;
+
+        Ok(string)
     }
-}
 
-impl From<u64> for Json
-{
-    fn from(number: u64) -> Json
+    pub fn write_canonical_to<W: io::Write>(&self, writer: &mut W) -> Result<(), Error>
     {
-        Json::Number(Number::Unsigned(number))
+        let mut adapter = IoAdapter::new(writer);
+        let result      = canonicalize(&mut adapter, self);
+
+        adapter.result(result)
     }
-}
 
-impl From<i32> for Json
-{
-    fn from(number: i32) -> Json
+    pub fn is_object(&self) -> bool
     {
-        Json::Number(Number::Integer(i64::from(number)))
+        matches!(self, Json::Object(_))
     }
-}
 
-impl From<i64> for Json
-{
-    fn from(number: i64) -> Json
+    pub fn is_array(&self) -> bool
     {
-        Json::Number(Number::Integer(number))
+        matches!(self, Json::Array(_))
     }
-}
 
-impl From<f64> for Json
-{
-    fn from(number: f64) -> Json
+    pub fn is_string(&self) -> bool
     {
-        Json::Number(Number::Float(number))
+        matches!(self, Json::String(_))
     }
-}
 
-impl From<bool> for Json
-{
-    fn from(value: bool) -> Json
+    pub fn is_number(&self) -> bool
     {
-        Json::Boolean(value)
+        matches!(self, Json::Number(_))
     }
-}
 
-impl From<()> for Json
-{
-    fn from(_: ()) -> Json
+    pub fn is_boolean(&self) -> bool
     {
-        Json::Null
+        matches!(self, Json::Boolean(_))
     }
-}
 
-impl From<Json> for HashMap<String, Json>
-{
-    fn from(json: Json) -> HashMap<String, Json>
+    pub fn is_null(&self) -> bool
     {
-        if let Json::Object(ref value) = json {
-            return value.clone();
+        matches!(self, Json::Null)
+    }
 
-        } else {
-            panic!("Expecting Json::Boolean, got {:?}", json);
+    pub fn as_object(&self) -> Option<&HashMap<String, Json>>
+    {
+        match self {
+            Json::Object(ref value) => Some(value),
+            _ => None,
         }
     }
-}
 
-impl From<Json> for Vec<Json>
-{
-    fn from(json: Json) -> Vec<Json>
+    pub fn as_object_mut(&mut self) -> Option<&mut HashMap<String, Json>>
     {
-        if let Json::Array(ref value) = json {
-            return value.clone();
+        match self {
+            Json::Object(ref mut value) => Some(value),
+            _ => None,
+        }
+    }
 
-        } else {
-            panic!("Expecting Json::Boolean, got {:?}", json);
+    pub fn as_array(&self) -> Option<&Vec<Json>>
+    {
+        match self {
+            Json::Array(ref value) => Some(value),
+            _ => None,
         }
     }
-}
 
-impl From<Json> for String
-{
-    fn from(json: Json) -> String
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Json>>
     {
-        if let Json::String(ref value) = json {
-            return value.clone();
+        match self {
+            Json::Array(ref mut value) => Some(value),
+            _ => None,
+        }
+    }
 
-        } else {
-            panic!("Expecting Json::String, got {:?}", json);
+    pub fn as_str(&self) -> Option<&str>
+    {
+        match self {
+            Json::String(ref value) => Some(value),
+            _ => None,
         }
     }
-}
 
-impl From<Json> for u64
-{
-    fn from(json: Json) -> u64
-    { 
-        match json {
-            Json::Number(value) => value.into(),
-            _ => {
-                panic!("Expecting Json::Number, got {:?}", json);
-            }
+    pub fn as_number(&self) -> Option<&Number>
+    {
+        match self {
+            Json::Number(ref value) => Some(value),
+            _ => None,
         }
     }
-}
 
-impl From<Json> for i64
-{
-    fn from(json: Json) -> i64
+    pub fn as_bool(&self) -> Option<bool>
+    {
+        match self {
+            Json::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// See `Number::as_u64`.
+    pub fn as_u64(&self) -> Option<u64>
+    {
+        self.as_number().and_then(Number::as_u64)
+    }
+
+    /// See `Number::as_i64`.
+    pub fn as_i64(&self) -> Option<i64>
+    {
+        self.as_number().and_then(Number::as_i64)
+    }
+
+    /// See `Number::as_f64`.
+    pub fn as_f64(&self) -> Option<f64>
+    {
+        self.as_number().map(Number::as_f64)
+    }
+
+    /// Moves the value out, leaving `Json::Null` in its place.
+    pub fn take(&mut self) -> Json
     {
-        if let Json::Number(ref value) = json {
-            value.clone().into()
+        mem::replace(self, Json::Null)
+    }
 
-        } else {
-            panic!("Expecting Json::Number, got {:?}", json);
+    pub fn into_object(self) -> Option<HashMap<String, Json>>
+    {
+        match self {
+            Json::Object(value) => Some(value),
+            _ => None,
         }
     }
-}
 
-impl From<Json> for f64
-{
-    fn from(json: Json) -> f64
+    pub fn into_array(self) -> Option<Vec<Json>>
     {
-        if let Json::Number(ref value) = json {
-            value.clone().into()
+        match self {
+            Json::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn into_string(self) -> Option<String>
+    {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
 
-        } else {
-            panic!("Expecting Json::Number, got {:?}", json);
+    pub fn into_number(self) -> Option<Number>
+    {
+        match self {
+            Json::Number(value) => Some(value),
+            _ => None,
         }
     }
 }
 
-impl From<Json> for bool
+// Compact output, or pretty output with `{:#}`.
+impl fmt::Display for Json
 {
-    fn from(json: Json) -> bool
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
     {
-        if let Json::Boolean(value) = json {
-            return value;
+        let format = if f.alternate() { Format::pretty() } else { Format::default() };
+
+        // The formats above write non-finite numbers as null, so this can
+        // only fail if the formatter does.
+        serialize(f, self, &format, 0).map_err(|_| fmt::Error)
+    }
+}
 
-        } else {
-            panic!("Expecting Json::Boolean, got {:?}", json);
+impl Json {
+    // Position of the kind of value in the order between kinds.
+    fn rank(&self) -> u8
+    {
+        match self {
+            Json::Null       => 0,
+            Json::Boolean(_) => 1,
+            Json::Number(_)  => 2,
+            Json::String(_)  => 3,
+            Json::Array(_)   => 4,
+            Json::Object(_)  => 5,
         }
     }
 }
 
-impl From<Json> for ()
+fn sorted(object: &HashMap<String, Json>) -> Vec<(&String, &Json)>
+{
+    let mut entries = object.iter().collect::<Vec<_>>();
+
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+impl Ord for Json
 {
-    fn from(json: Json)
+    fn cmp(&self, other: &Json) -> Ordering
     {
-        if json != Json::Null {
-            panic!("Expecting Json::Null, got {:?}", json);
+        match (self, other) {
+            (Json::Boolean(a), Json::Boolean(b)) => a.cmp(b),
+            (Json::Number(a), Json::Number(b))   => a.cmp(b),
+            (Json::String(a), Json::String(b))   => a.cmp(b),
+            (Json::Array(a), Json::Array(b))     => a.cmp(b),
+            (Json::Object(a), Json::Object(b))   => sorted(a).cmp(&sorted(b)),
+            _ => self.rank().cmp(&other.rank()),
         }
     }
 }
 
+impl PartialOrd for Json
+{
+    fn partial_cmp(&self, other: &Json) -> Option<Ordering>
+    {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for Json
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.rank().hash(state);
+
+        match self {
+            Json::Object(value)  => { sorted(value).hash(state); },
+            Json::Array(value)   => { value.hash(state); },
+            Json::String(value)  => { value.hash(state); },
+            Json::Number(value)  => { value.hash(state); },
+            Json::Boolean(value) => { value.hash(state); },
+            Json::Null => {},
+        }
+    }
+}
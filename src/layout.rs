@@ -0,0 +1,99 @@
+use std::fmt::{ self, Write };
+
+use crate::color::punctuation;
+use crate::error::Error;
+use crate::format::Format;
+use crate::json::Json;
+use crate::serializer::{ entries, scalar, write_colon, write_key };
+
+// Wadler-style layout, used when `Format::max_width` is set: every array and
+// object is a group, written flat when it fits in the room left on its line
+// and broken over several lines otherwise. Measuring stops as soon as the
+// room is exceeded, so a group never costs more than the width of a line.
+
+// Whether `json` written flat takes at most `room` characters.
+pub fn fits(json: &Json, format: &Format, room: usize) -> bool
+{
+    let mut measure = Measure::new(room);
+
+    flat(&mut measure, json, format).is_ok()
+}
+
+// Writes `json` on a single line, with the spacing of pretty output.
+pub fn flat<W: Write>(writer: &mut W, json: &Json, format: &Format) -> Result<(), Error>
+{
+    match json {
+        Json::Array(ref value) if !value.is_empty() => {
+            punctuation(writer, format, "[")?;
+            writer.write_char(' ')?;
+            for (i, elem) in value.iter().enumerate() {
+                if i > 0 {
+                    punctuation(writer, format, ",")?;
+                    writer.write_char(' ')?;
+                }
+                flat(writer, elem, format)?;
+            }
+            writer.write_char(' ')?;
+            punctuation(writer, format, "]")?;
+        },
+        Json::Object(ref value) if !value.is_empty() => {
+            punctuation(writer, format, "{")?;
+            writer.write_char(' ')?;
+            for (i, (k, v)) in entries(value, format).into_iter().enumerate() {
+                if i > 0 {
+                    punctuation(writer, format, ",")?;
+                    writer.write_char(' ')?;
+                }
+                write_key(writer, k, format)?;
+                write_colon(writer, format)?;
+                flat(writer, v, format)?;
+            }
+            writer.write_char(' ')?;
+            punctuation(writer, format, "}")?;
+        },
+        Json::Array(_)  => { punctuation(writer, format, "[]")?; },
+        Json::Object(_) => { punctuation(writer, format, "{}")?; },
+        _ => { scalar(writer, json, format)?; },
+    }
+
+    Ok(())
+}
+
+// Counts the characters written to it, failing once there are more than
+// `limit` of them. ANSI escape sequences take no room on screen and aren't
+// counted.
+pub struct Measure {
+    pub width: usize,
+    limit:     usize,
+    escape:    bool,
+}
+
+impl Measure {
+    pub fn new(limit: usize) -> Measure
+    {
+        Measure { width: 0, limit, escape: false }
+    }
+}
+
+impl Write for Measure
+{
+    fn write_str(&mut self, string: &str) -> fmt::Result
+    {
+        for chr in string.chars() {
+            if self.escape {
+                // Sequences end with a byte between @ and ~, past the [.
+                self.escape = chr == '[' || !('@'..='~').contains(&chr);
+            } else if chr == '\x1b' {
+                self.escape = true;
+            } else {
+                self.width += 1;
+            }
+        }
+
+        if self.width > self.limit {
+            return Err(fmt::Error);
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,41 @@
+#[macro_use]
+mod macros;
+
+mod canonical;
+mod color;
+mod convert;
+mod cst;
 mod error;
+mod escape;
+mod float;
+mod format;
+mod index;
 mod json;
+mod layout;
+mod ndjson;
 mod number;
 mod parser;
+mod patch;
+mod path;
+mod pointer;
+mod serializer;
+mod tape;
+mod transcode;
+mod writer;
 
-pub use self::error::Error;
+pub use self::color::Palette;
+pub use self::cst::Document;
+pub use self::error::{ Error, Position };
+pub use self::format::{ Format, KeyOrder, NonFinite };
+pub use self::index::JsonIndex;
 pub use self::json::Json;
+pub use self::ndjson::{ Ndjson, NdjsonIter };
 pub use self::number::Number;
+pub use self::patch::{ JsonPatch, PatchOperation };
+pub use self::path::{ JsonPath, QueryMatch };
+pub use self::pointer::JsonPointer;
+pub use self::tape::{ Tape, TapeElements, TapeEntries, TapeRef };
+pub use self::transcode::transcode;
+pub use self::writer::JsonWriter;
 
 pub use self::parser::*;
@@ -0,0 +1,159 @@
+/// Builds a `Json` from JSON-like syntax.
+///
+/// Literals and `null` are written as in JSON, and anywhere a value goes any
+/// Rust expression converting into `Json` can go as well. Keys are string
+/// literals or expressions converting into `String`. A single trailing comma
+/// is accepted after the last element or entry.
+///
+/// Arrays and objects whose elements, keys and values are each a single
+/// token, like literals, names or nested arrays and objects, expand in one
+/// step whatever their size. Others are taken apart a few elements at a
+/// time, so a very long one may need a higher `recursion_limit`.
+///
+/// ```
+/// # #[macro_use] extern crate simple_json;
+/// # fn main() {
+/// let w = 800;
+/// let a = "a";
+///
+/// let json = json!({
+///     "size": { "x": w, "y": 600 },
+///     "tags": [a, "b"],
+///     "parent": null,
+/// });
+///
+/// assert_eq!(json["size"]["x"], json!(800));
+/// # }
+/// ```
+///
+/// Empty elements or entries aren't, as they aren't valid JSON either:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate simple_json;
+/// # fn main() {
+/// let json = json!([1,,]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate simple_json;
+/// # fn main() {
+/// let json = json!({ "a": 1,, });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! json {
+    // Arrays: single token elements are taken eight at a time and negative
+    // numbers one at a time, others have their tokens moved into the
+    // parentheses until a comma ends them. Each element is then added to the
+    // brackets.
+    (@array [$($elems:expr,)*] () ()) => {
+        ::std::vec![$($elems,)*]
+    };
+    (@array [$($elems:expr,)*] ($($value:tt)+) ()) => {
+        ::std::vec![$($elems,)* $crate::json!($($value)+)]
+    };
+    (@array [$($elems:expr,)*] () ($a:tt, $b:tt, $c:tt, $d:tt, $e:tt, $f:tt, $g:tt, $h:tt, $($rest:tt)*)) => {
+        $crate::json!(@array [
+            $($elems,)*
+            $crate::json!($a), $crate::json!($b), $crate::json!($c), $crate::json!($d),
+            $crate::json!($e), $crate::json!($f), $crate::json!($g), $crate::json!($h),
+        ] () ($($rest)*))
+    };
+    (@array [$($elems:expr,)*] () (, $($rest:tt)*)) => {
+        ::std::compile_error!("missing element before comma")
+    };
+    (@array [$($elems:expr,)*] () ($value:tt , $($rest:tt)*)) => {
+        $crate::json!(@array [$($elems,)* $crate::json!($value),] () ($($rest)*))
+    };
+    (@array [$($elems:expr,)*] () (- $value:tt , $($rest:tt)*)) => {
+        $crate::json!(@array [$($elems,)* $crate::json!(-$value),] () ($($rest)*))
+    };
+    (@array [$($elems:expr,)*] ($($value:tt)+) (, $($rest:tt)*)) => {
+        $crate::json!(@array [$($elems,)* $crate::json!($($value)+),] () ($($rest)*))
+    };
+    (@array [$($elems:expr,)*] ($($value:tt)*) ($next:tt $($rest:tt)*)) => {
+        $crate::json!(@array [$($elems,)*] ($($value)* $next) ($($rest)*))
+    };
+
+    // Objects: the same for keys, ended by a colon, then for values, ended
+    // by a comma, each entry being inserted into `$object` as it completes.
+    (@object $object:ident () ()) => {};
+    (@object $object:ident () ($a:tt : $x:tt, $b:tt : $y:tt, $c:tt : $z:tt, $d:tt : $w:tt, $($rest:tt)*)) => {
+        $crate::json!(@insert $object ($a) ($x));
+        $crate::json!(@insert $object ($b) ($y));
+        $crate::json!(@insert $object ($c) ($z));
+        $crate::json!(@insert $object ($d) ($w));
+        $crate::json!(@object $object () ($($rest)*));
+    };
+    (@object $object:ident () (, $($rest:tt)*)) => {
+        ::std::compile_error!("missing entry before comma")
+    };
+    (@object $object:ident () ($key:tt : $($rest:tt)*)) => {
+        $crate::json!(@value $object [$key] () ($($rest)*))
+    };
+    (@object $object:ident ($($key:tt)+) (: $($rest:tt)*)) => {
+        $crate::json!(@value $object [$($key)+] () ($($rest)*))
+    };
+    (@object $object:ident ($($key:tt)*) ()) => {
+        ::std::compile_error!("missing colon and value after key")
+    };
+    (@object $object:ident ($($key:tt)*) ($next:tt $($rest:tt)*)) => {
+        $crate::json!(@object $object ($($key)* $next) ($($rest)*))
+    };
+    (@value $object:ident [$($key:tt)+] () ()) => {
+        ::std::compile_error!("missing value after colon")
+    };
+    (@value $object:ident [$($key:tt)+] ($($value:tt)+) ()) => {
+        $crate::json!(@insert $object ($($key)+) ($($value)+));
+    };
+    (@value $object:ident [$($key:tt)+] () (, $($rest:tt)*)) => {
+        ::std::compile_error!("missing value after colon")
+    };
+    (@value $object:ident [$($key:tt)+] () ($value:tt , $($rest:tt)*)) => {
+        $crate::json!(@insert $object ($($key)+) ($value));
+        $crate::json!(@object $object () ($($rest)*));
+    };
+    (@value $object:ident [$($key:tt)+] () (- $value:tt , $($rest:tt)*)) => {
+        $crate::json!(@insert $object ($($key)+) (-$value));
+        $crate::json!(@object $object () ($($rest)*));
+    };
+    (@value $object:ident [$($key:tt)+] ($($value:tt)+) (, $($rest:tt)*)) => {
+        $crate::json!(@insert $object ($($key)+) ($($value)+));
+        $crate::json!(@object $object () ($($rest)*));
+    };
+    (@value $object:ident [$($key:tt)+] ($($value:tt)*) ($next:tt $($rest:tt)*)) => {
+        $crate::json!(@value $object [$($key)+] ($($value)* $next) ($($rest)*))
+    };
+    (@insert $object:ident ($($key:tt)+) ($($value:tt)+)) => {
+        $object.insert(::std::string::String::from($($key)+), $crate::json!($($value)+));
+    };
+
+    (,) => {
+        ::std::compile_error!("missing value before comma")
+    };
+    (null) => {
+        $crate::Json::Null
+    };
+    ([ $($value:tt),* $(,)? ]) => {
+        $crate::Json::Array(::std::vec![$($crate::json!($value)),*])
+    };
+    ([ $($tt:tt)* ]) => {
+        $crate::Json::Array($crate::json!(@array [] () ($($tt)*)))
+    };
+    ({ $($key:tt : $value:tt),* $(,)? }) => {{
+        #[allow(unused_mut)]
+        let mut object = ::std::collections::HashMap::new();
+        $($crate::json!(@insert object ($key) ($value));)*
+        $crate::Json::Object(object)
+    }};
+    ({ $($tt:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut object = ::std::collections::HashMap::new();
+        $crate::json!(@object object () ($($tt)*));
+        $crate::Json::Object(object)
+    }};
+    ($other:expr) => {
+        $crate::Json::from($other)
+    };
+}
@@ -0,0 +1,278 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{ self, Read };
+use std::ops::Range;
+use std::panic;
+use std::path::Path;
+use std::sync::{ Arc, Condvar, Mutex };
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::sync::mpsc::{ self, Receiver };
+use std::thread;
+
+use crate::error::Error;
+use crate::json::Json;
+
+type Record = (usize, Result<Json, Error>);
+
+/// Parallel reader for newline-delimited JSON.
+///
+/// The input is split on line boundaries into chunks of `chunk_lines` lines,
+/// which are parsed by `threads` workers. Blank lines are skipped. In ordered
+/// mode, workers stay at most `2 * threads` chunks ahead of the one the
+/// iterator is waiting for, which bounds how many parsed chunks are held.
+#[derive(Clone, Debug)]
+pub struct Ndjson {
+    threads:     usize,
+    chunk_lines: usize,
+    ordered:     bool,
+}
+
+impl Ndjson {
+    pub fn new() -> Ndjson
+    {
+        let threads = match thread::available_parallelism() {
+            Ok(count) => count.get(),
+            Err(_)    => 1,
+        };
+
+        Ndjson {
+            threads,
+            chunk_lines: 1024,
+            ordered:     true,
+        }
+    }
+
+    pub fn threads(mut self, threads: usize) -> Ndjson
+    {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn chunk_lines(mut self, lines: usize) -> Ndjson
+    {
+        self.chunk_lines = lines.max(1);
+        self
+    }
+
+    /// When disabled, records are yielded as soon as their chunk is parsed.
+    pub fn ordered(mut self, ordered: bool) -> Ndjson
+    {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Parses `text`, which is shared with the workers as it is: a `String`,
+    /// an `Arc<str>` or a `&'static str` is never copied.
+    pub fn parse<T: AsRef<str> + Send + Sync + 'static>(&self, text: T) -> NdjsonIter
+    {
+        let text   = Arc::new(text);
+        let chunks = Arc::new(self.split((*text).as_ref()));
+
+        let next     = Arc::new(AtomicUsize::new(0));
+        let progress = Arc::new(Progress { next: Mutex::new(0), advanced: Condvar::new() });
+        let window   = if self.ordered { self.threads * 2 } else { usize::MAX };
+        let (sender, receiver) = mpsc::sync_channel(self.threads * 2);
+
+        for _ in 0..self.threads.min(chunks.len()) {
+            let text     = Arc::clone(&text);
+            let chunks   = Arc::clone(&chunks);
+            let next     = Arc::clone(&next);
+            let progress = Arc::clone(&progress);
+            let sender   = sender.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+
+                    let (first_line, range) = match chunks.get(index) {
+                        Some(chunk) => chunk.clone(),
+                        None        => { break },
+                    };
+
+                    progress.wait(index, window);
+
+                    // A panicking chunk is reported rather than silently
+                    // cutting the output short.
+                    let chunk   = &(*text).as_ref()[range];
+                    let records = panic::catch_unwind(|| parse_chunk(chunk, first_line))
+                        .unwrap_or_else(|cause| vec![(first_line, Err(Error::Panicked(message(&*cause))))]);
+
+                    // The iterator was dropped, nobody is listening anymore.
+                    if sender.send((index, records)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        NdjsonIter {
+            receiver,
+            progress,
+            ordered: self.ordered,
+            next:    0,
+            pending: BTreeMap::new(),
+            current: Vec::new().into_iter(),
+        }
+    }
+
+    pub fn parse_reader<R: Read>(&self, mut reader: R) -> io::Result<NdjsonIter>
+    {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+
+        Ok(self.parse(text))
+    }
+
+    pub fn parse_file<P: AsRef<Path>>(&self, path: P) -> io::Result<NdjsonIter>
+    {
+        Ok(self.parse(fs::read_to_string(path)?))
+    }
+
+    // Returns the byte range and the number of the first line of each chunk.
+    fn split(&self, text: &str) -> Vec<(usize, Range<usize>)>
+    {
+        let mut chunks = vec![];
+        let mut start  = 0;
+        let mut line   = 1;
+        let mut lines  = 0;
+
+        for (offset, byte) in text.bytes().enumerate() {
+            if byte != b'\n' {
+                continue;
+            }
+
+            lines += 1;
+
+            if lines == self.chunk_lines {
+                chunks.push((line, start..offset + 1));
+
+                start  = offset + 1;
+                line  += lines;
+                lines  = 0;
+            }
+        }
+
+        if start < text.len() {
+            chunks.push((line, start..text.len()));
+        }
+
+        chunks
+    }
+}
+
+impl Default for Ndjson
+{
+    fn default() -> Ndjson
+    {
+        Ndjson::new()
+    }
+}
+
+fn parse_chunk(chunk: &str, first_line: usize) -> Vec<Record>
+{
+    let mut records = vec![];
+
+    for (number, line) in chunk.split('\n').enumerate() {
+        let line = line.trim_end_matches('\r');
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        records.push((first_line + number, Json::parse(line)));
+    }
+
+    records
+}
+
+fn message(cause: &(dyn Any + Send)) -> String
+{
+    match (cause.downcast_ref::<&str>(), cause.downcast_ref::<String>()) {
+        (Some(message), _) => String::from(*message),
+        (_, Some(message)) => message.clone(),
+        _ => String::from("worker thread panicked"),
+    }
+}
+
+// The chunk the iterator waits for, so ordered workers don't run too far
+// ahead of it.
+struct Progress {
+    next:     Mutex<usize>,
+    advanced: Condvar,
+}
+
+impl Progress {
+    // Blocks until chunk `index` is less than `window` chunks ahead.
+    fn wait(&self, index: usize, window: usize)
+    {
+        let mut next = self.next.lock().unwrap();
+
+        while index >= next.saturating_add(window) {
+            next = self.advanced.wait(next).unwrap();
+        }
+    }
+
+    fn advance(&self, next: usize)
+    {
+        *self.next.lock().unwrap() = next;
+        self.advanced.notify_all();
+    }
+}
+
+/// Yields `(line number, parse result)` pairs, numbering lines from 1. A
+/// chunk whose worker panicked gives a single `Error::Panicked` in place of
+/// its records.
+pub struct NdjsonIter {
+    receiver: Receiver<(usize, Vec<Record>)>,
+    progress: Arc<Progress>,
+    ordered:  bool,
+    next:     usize,
+    pending:  BTreeMap<usize, Vec<Record>>,
+    current:  std::vec::IntoIter<Record>,
+}
+
+impl NdjsonIter {
+    fn next_chunk(&mut self) -> Option<Vec<Record>>
+    {
+        if !self.ordered {
+            return self.receiver.recv().ok().map(|(_, records)| records);
+        }
+
+        loop {
+            if let Some(records) = self.pending.remove(&self.next) {
+                self.next += 1;
+                self.progress.advance(self.next);
+                return Some(records);
+            }
+
+            let (index, records) = self.receiver.recv().ok()?;
+            self.pending.insert(index, records);
+        }
+    }
+}
+
+impl Iterator for NdjsonIter
+{
+    type Item = (usize, Result<Json, Error>);
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        loop {
+            if let Some(record) = self.current.next() {
+                return Some(record);
+            }
+
+            self.current = self.next_chunk()?.into_iter();
+        }
+    }
+}
+
+impl Drop for NdjsonIter
+{
+    // Releases the waiting workers, which then find nobody listening.
+    fn drop(&mut self)
+    {
+        self.progress.advance(usize::MAX);
+    }
+}
@@ -1,4 +1,16 @@
-#[derive(Clone, Debug, PartialEq)]
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{ Hash, Hasher };
+
+use crate::error::Error;
+use crate::float;
+
+/// Numbers compare by their mathematical value whatever the variant, so
+/// `Unsigned(1)`, `Integer(1)` and `Float(1.0)` are all equal, exactly and
+/// without going through `f64`. For a total order, `-0.0` equals `0.0` and
+/// NaN equals itself and is greater than everything else.
+#[derive(Clone, Debug)]
 pub enum Number
 {
     Unsigned(u64),
@@ -6,75 +18,242 @@ pub enum Number
     Float(f64),
 }
 
-impl Number
-{
-    pub fn to_string(&self) -> String
+// Smallest floats too big for a u64 and an i64.
+const TWO_64: f64 = 18446744073709551616.0;
+const TWO_63: f64 = 9223372036854775808.0;
+
+impl Number {
+    /// The value as a `u64` if it is exactly one, floats included.
+    pub fn as_u64(&self) -> Option<u64>
     {
-        match self {
-            Number::Unsigned(value) => value.to_string(),
-            Number::Integer(value)  => value.to_string(),
-            Number::Float(value)    => value.to_string(),
+        match *self {
+            Number::Unsigned(value) => Some(value),
+            Number::Integer(value)  => if value >= 0 { Some(value as u64) } else { None },
+            Number::Float(value) if value.fract() == 0.0 && (0.0..TWO_64).contains(&value) => {
+                Some(value as u64)
+            },
+            Number::Float(_) => None,
+        }
+    }
+
+    /// The value as an `i64` if it is exactly one, floats included.
+    pub fn as_i64(&self) -> Option<i64>
+    {
+        match *self {
+            Number::Unsigned(value) => if value <= i64::MAX as u64 { Some(value as i64) } else { None },
+            Number::Integer(value)  => Some(value),
+            Number::Float(value) if value.fract() == 0.0 && (-TWO_63..TWO_63).contains(&value) => {
+                Some(value as i64)
+            },
+            Number::Float(_) => None,
+        }
+    }
+
+    /// The value as an `f64`, rounding integers beyond 2^53 to the nearest
+    /// float.
+    pub fn as_f64(&self) -> f64
+    {
+        match *self {
+            Number::Unsigned(value) => value as f64,
+            Number::Integer(value)  => value as f64,
+            Number::Float(value)    => value,
+        }
+    }
+
+    // Integers widened so both variants fit, floats as they are.
+    fn split(&self) -> Result<i128, f64>
+    {
+        match *self {
+            Number::Unsigned(value) => Ok(i128::from(value)),
+            Number::Integer(value)  => Ok(i128::from(value)),
+            Number::Float(value)    => Err(value),
         }
     }
 }
 
-impl From<u64> for Number
+// Compares an integer with a float exactly.
+fn compare_mixed(integer: i128, float: f64) -> Ordering
+{
+    if float.is_nan() {
+        return Ordering::Less;
+    }
+
+    // Both bounds are out of reach for any u64 or i64.
+    if float >= TWO_64 {
+        return Ordering::Less;
+    }
+    if float < -TWO_63 {
+        return Ordering::Greater;
+    }
+
+    // Within that range the integral part converts exactly.
+    let whole = float.trunc();
+
+    integer.cmp(&(whole as i128)).then_with(|| {
+        0.0.partial_cmp(&(float - whole)).unwrap()
+    })
+}
+
+fn compare_floats(a: f64, b: f64) -> Ordering
 {
-    fn from(number: u64) -> Number
+    match (a.is_nan(), b.is_nan()) {
+        (true, true)   => Ordering::Equal,
+        (true, false)  => Ordering::Greater,
+        (false, true)  => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+impl Ord for Number
+{
+    fn cmp(&self, other: &Number) -> Ordering
     {
-        Number::Unsigned(number)
+        match (self.split(), other.split()) {
+            (Ok(a), Ok(b))   => a.cmp(&b),
+            (Ok(a), Err(b))  => compare_mixed(a, b),
+            (Err(a), Ok(b))  => compare_mixed(b, a).reverse(),
+            (Err(a), Err(b)) => compare_floats(a, b),
+        }
     }
 }
 
-impl From<i64> for Number
+impl PartialOrd for Number
 {
-    fn from(number: i64) -> Number
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering>
     {
-        Number::Integer(number)
+        Some(self.cmp(other))
     }
 }
 
-impl From<f64> for Number
+impl PartialEq for Number
 {
-    fn from(number: f64) -> Number
+    fn eq(&self, other: &Number) -> bool
     {
-        Number::Float(number)
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl From<Number> for u64
+impl Eq for Number {}
+
+// Floats holding an integer some u64 or i64 could hold hash like it, so equal
+// numbers always hash the same.
+impl Hash for Number
 {
-    fn from(number: Number) -> u64
+    fn hash<H: Hasher>(&self, state: &mut H)
     {
-        match number {
-            Number::Unsigned(value) => value,
-            Number::Integer(value)  => value as u64,
-            Number::Float(value)    => value as u64,
+        match self.split() {
+            Ok(integer) => { integer.hash(state); },
+            Err(float) if float.is_nan() => { f64::NAN.to_bits().hash(state); },
+            Err(float) if float.fract() == 0.0 && (-TWO_63..TWO_64).contains(&float) => {
+                (float as i128).hash(state);
+            },
+            Err(float) => { float.to_bits().hash(state); },
         }
     }
 }
 
-impl From<Number> for i64
+// Floats always have a fraction or an exponent, non-finite ones are written
+// as the JSON5 literals NaN, Infinity and -Infinity.
+impl fmt::Display for Number
 {
-    fn from(number: Number) -> i64
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
     {
-        match number {
-            Number::Unsigned(value) => value as i64,
-            Number::Integer(value)  => value,
-            Number::Float(value)    => value as i64,
+        match self {
+            Number::Unsigned(value) => write!(f, "{}", value),
+            Number::Integer(value)  => write!(f, "{}", value),
+            Number::Float(value)    => f.write_str(&float::format(*value)),
         }
     }
 }
 
+macro_rules! from_primitive {
+    ($variant:ident, $wide:ty, $($t:ty),*) => {$(
+        impl From<$t> for Number
+        {
+            fn from(number: $t) -> Number
+            {
+                Number::$variant(number as $wide)
+            }
+        }
+    )*};
+}
+
+from_primitive!(Unsigned, u64, u8, u16, u32, u64, usize);
+from_primitive!(Integer,  i64, i8, i16, i32, i64, isize);
+from_primitive!(Float,    f64, f32, f64);
+
+// Integers convert when the value fits, floats included as long as they hold
+// an integer, see `as_u64`.
+macro_rules! try_into_integer {
+    ($exact:ident, $($t:ident),*) => {$(
+        impl TryFrom<Number> for $t
+        {
+            type Error = Error;
+
+            fn try_from(number: Number) -> Result<$t, Error>
+            {
+                $t::try_from(&number)
+            }
+        }
+
+        impl<'a> TryFrom<&'a Number> for $t
+        {
+            type Error = Error;
+
+            fn try_from(number: &'a Number) -> Result<$t, Error>
+            {
+                number.$exact()
+                    .and_then(|value| $t::try_from(value).ok())
+                    .ok_or_else(|| Error::OutOfRange(number.to_string(), stringify!($t)))
+            }
+        }
+    )*};
+}
+
+try_into_integer!(as_u64, u8, u16, u32, u64, usize);
+try_into_integer!(as_i64, i8, i16, i32, i64, isize);
+
+// Never fails, see `as_f64`.
 impl From<Number> for f64
 {
     fn from(number: Number) -> f64
     {
-        match number {
-            Number::Unsigned(value) => value as f64,
-            Number::Integer(value)  => value as f64,
-            Number::Float(value)    => value,
-        }
+        number.as_f64()
     }
 }
 
+impl<'a> From<&'a Number> for f64
+{
+    fn from(number: &'a Number) -> f64
+    {
+        number.as_f64()
+    }
+}
+
+// Rounds to the nearest f32, failing for finite values too big for one.
+impl TryFrom<Number> for f32
+{
+    type Error = Error;
+
+    fn try_from(number: Number) -> Result<f32, Error>
+    {
+        f32::try_from(&number)
+    }
+}
+
+impl<'a> TryFrom<&'a Number> for f32
+{
+    type Error = Error;
+
+    fn try_from(number: &'a Number) -> Result<f32, Error>
+    {
+        let value  = number.as_f64();
+        let single = value as f32;
+
+        if value.is_finite() && single.is_infinite() {
+            return Err(Error::OutOfRange(number.to_string(), "f32"));
+        }
+
+        Ok(single)
+    }
+}
@@ -1,3 +1,6 @@
+// The tokenizers predate some lints, they're kept as written.
+#![allow(ellipsis_inclusive_range_patterns, clippy::match_single_binding, clippy::question_mark)]
+
 mod node;
 mod null;
 mod boolean;
@@ -11,6 +14,7 @@ pub use self::null::null;
 pub use self::boolean::boolean;
 pub use self::number::number;
 pub use self::string::string;
+pub(crate) use self::string::string_into;
 pub use self::array::array;
 pub use self::object::object;
 
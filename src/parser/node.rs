@@ -20,22 +20,22 @@ pub fn node(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
                 slice.next();
             },
             'n' => {
-                content = null(slice);
+                content = Ok(null(slice)?);
             },
             'f' | 't' => {
-                content = boolean(slice);
+                content = Ok(boolean(slice)?);
             },
             '0'...'9' | '-' => {
-                content = number(slice);
+                content = Ok(number(slice)?);
             },
             '"' => {
-                content = string(slice);
+                content = Ok(string(slice)?);
             },
             '[' => {
-                content = array(slice);
+                content = Ok(array(slice)?);
             },
             '{' => {
-                content = object(slice);
+                content = Ok(object(slice)?);
             },
 
             _ => { break 'tokenizer }
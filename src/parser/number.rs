@@ -2,6 +2,7 @@ use std::iter::Peekable;
 use std::str::Chars;
 
 use crate::error::Error;
+use crate::float;
 use crate::json::Json;
 use crate::number::Number;
 
@@ -20,7 +21,6 @@ pub fn number(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
         AfterExp,
         AfterExpSign,
         Exponent,
-        End,
     }
 
     let mut stage       = Stages::Sign;
@@ -47,8 +47,6 @@ pub fn number(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
             Stages::AfterSign => match current {
                 '0'       => { stage = Stages::AfterZero; token.push(current); slice.next(); },
                 '1'...'9' => { stage = Stages::Integer;   token.push(current); slice.next(); },
-                '.'       => { stage = Stages::AfterDot;  token.push(current); slice.next(); },
-                'e' | 'E' => { stage = Stages::AfterExp;  token.push(current); slice.next(); },
 
                 // Waiting for a number.
                 _ => {
@@ -68,7 +66,6 @@ pub fn number(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
             },
             Stages::AfterDot => match current {
                 '0'...'9' => { stage = Stages::Fraction; token.push(current); slice.next(); },
-                'e' | 'E' => { stage = Stages::AfterExp; token.push(current); slice.next(); },
 
                 // Waiting for a number.
                 _ => {
@@ -82,8 +79,7 @@ pub fn number(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
             },
             Stages::AfterExp => match current {
                 '+' | '-' => { stage = Stages::AfterExpSign; token.push(current); slice.next(); },
-                '0'       => { stage = Stages::End;          token.push(current); slice.next(); },
-                '1'...'9' => { stage = Stages::Exponent;     token.push(current); slice.next(); },
+                '0'..='9' => { stage = Stages::Exponent;     token.push(current); slice.next(); },
 
                 // Waiting for a number.
                 _ => {
@@ -91,7 +87,7 @@ pub fn number(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
                 },
             },
             Stages::AfterExpSign => match current {
-                '1'...'9' => { stage = Stages::Exponent; token.push(current); slice.next(); },
+                '0'..='9' => { stage = Stages::Exponent; token.push(current); slice.next(); },
 
                 // Waiting for a number.
                 _ => {
@@ -102,23 +98,35 @@ pub fn number(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
                 '0'...'9' => { token.push(current); slice.next(); },
                 _         => { break 'tokenizer; },
             },
-            Stages::End => match current {
-                _         => { break 'tokenizer; },
-            },
         }
     }
 
     Ok(Json::Number(
         match stage {
             Stages::Integer | Stages::AfterZero => {
+                // Integers that don't fit are kept as the closest float.
                 if is_unsigned {
-                    Number::Unsigned(token.parse::<u64>().unwrap())
+                    match token.parse::<u64>() {
+                        Ok(value) => Number::Unsigned(value),
+                        Err(_)    => Number::Float(float::parse(&token)),
+                    }
+                } else if token == "-0" {
+                    // Integers have no negative zero, keep the sign.
+                    Number::Float(-0.0)
                 } else {
-                    Number::Integer(token.parse::<i64>().unwrap())
+                    match token.parse::<i64>() {
+                        Ok(value) => Number::Integer(value),
+                        Err(_)    => Number::Float(float::parse(&token)),
+                    }
                 }
             },
+            Stages::Fraction | Stages::Exponent => {
+                Number::Float(float::parse(&token))
+            },
+
+            // The number was cut short.
             _ => {
-                Number::Float(token.parse::<f64>().unwrap())
+                return Err(Error::UnexpectedEof);
             },
         }
     ))
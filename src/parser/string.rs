@@ -7,8 +7,18 @@ use crate::json::Json;
 
 pub fn string(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
 {
-    let mut token   = String::new();
-    let mut unicode = String::new();
+    let mut token = String::new();
+
+    string_into(slice, &mut token)?;
+
+    Ok(Json::String(token))
+}
+
+// Appends the decoded string to `token` instead of allocating a new one.
+pub(crate) fn string_into(slice: &mut Peekable<&mut Chars>, token: &mut String) -> Result<(), Error>
+{
+    let mut unicode   = String::new();
+    let mut surrogate = None;
 
     #[derive(Debug, PartialEq)]
     enum Stages {
@@ -17,6 +27,8 @@ pub fn string(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
         Escaped,
         EscapedUnicode,
         AfterUnicode,
+        LowSurrogate,
+        LowSurrogateEscaped,
         End,
     }
 
@@ -40,6 +52,11 @@ pub fn string(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
             Stages::Unescaped => match current {
                 '\\' => { stage = Stages::Escaped; slice.next(); },
                 '"'  => { stage = Stages::End;     slice.next(); },
+
+                // Control characters must be escaped.
+                '\u{0}'..='\u{1f}' => {
+                    return Err(Error::InvalidCharacter(format!("U+{:04X}", current as u32)));
+                },
                 _    => { token.push(current);     slice.next(); },
             },
             Stages::Escaped => match current {
@@ -59,20 +76,62 @@ pub fn string(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
                 },
             },
             Stages::EscapedUnicode => match current {
-                '0'...'9' => { unicode.push(current); slice.next(); },
-                'A'...'F' => { unicode.push(current); slice.next(); },
-                'a'...'f' => { unicode.push(current); slice.next(); },
-                _ => { stage = Stages::AfterUnicode; }
+                '0'..='9' | 'A'..='F' | 'a'..='f' => {
+                    unicode.push(current);
+                    slice.next();
+
+                    if unicode.len() == 4 {
+                        stage = Stages::AfterUnicode;
+                    }
+                },
+
+                // Waiting for four hexadecimal digits.
+                _ => {
+                    return Err(Error::InvalidCharacter(format!("U+{}", unicode)));
+                },
             },
             Stages::AfterUnicode => {
-                if unicode.len() != 4 {
-                    return Err(Error::InvalidCharacter(format!("U+{}", unicode)));
+                let code = u32::from_str_radix(unicode.as_str(), 16).unwrap();
+
+                match (surrogate, code) {
+                    // Characters outside the BMP come as a UTF-16 surrogate pair.
+                    (None, 0xD800..=0xDBFF) => {
+                        surrogate = Some(code);
+                        stage     = Stages::LowSurrogate;
+                    },
+                    (Some(high), 0xDC00..=0xDFFF) => {
+                        let code = 0x10000 + ((high - 0xD800) << 10) + (code - 0xDC00);
+
+                        token.push(char::from_u32(code).unwrap());
+                        surrogate = None;
+                        stage     = Stages::Unescaped;
+                    },
+                    (None, _) if char::from_u32(code).is_some() => {
+                        token.push(char::from_u32(code).unwrap());
+                        stage = Stages::Unescaped;
+                    },
+
+                    // Unpaired surrogate.
+                    _ => {
+                        return Err(Error::InvalidCharacter(format!("U+{}", unicode)));
+                    },
                 }
+            },
+            Stages::LowSurrogate => match current {
+                '\\' => { stage = Stages::LowSurrogateEscaped; slice.next(); },
 
-                let code = u32::from_str_radix(unicode.as_str(), 16).unwrap();
-                token.push(char::from_u32(code).unwrap());
+                // Waiting for the second half of the pair.
+                _ => {
+                    return Err(Error::InvalidCharacter(format!("U+{:X}", surrogate.unwrap())));
+                },
+            },
+            Stages::LowSurrogateEscaped => match current {
+                'u' => { stage = Stages::EscapedUnicode; unicode = String::new(); slice.next(); },
 
-                stage = Stages::Unescaped;
+                // Waiting for the second half of the pair.
+                _ => {
+                    return Err(Error::InvalidCharacter(format!("U+{:X}", surrogate.unwrap())));
+                },
             },
             Stages::End => match current {
                 _ => { break 'tokenizer; },
@@ -80,6 +139,11 @@ pub fn string(slice: &mut Peekable<&mut Chars>) -> Result<Json, Error>
         }
     }
 
-    Ok(Json::String(token))
+    // The closing quotation mark never came.
+    if stage != Stages::End {
+        return Err(Error::UnexpectedEof);
+    }
+
+    Ok(())
 }
 
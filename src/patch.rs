@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+
+use crate::convert::kind;
+use crate::error::Error;
+use crate::json::Json;
+use crate::pointer::JsonPointer;
+
+// Above this many pairs of elements to align, arrays are compared position
+// by position instead.
+const MAX_ALIGNMENT: usize = 1 << 20;
+
+/// A JSON Patch as defined by RFC 6902: operations applied in order, each
+/// to the result of the previous one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonPatch {
+    pub operations: Vec<PatchOperation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PatchOperation {
+    Add { path: JsonPointer, value: Json },
+    Remove { path: JsonPointer },
+    Replace { path: JsonPointer, value: Json },
+    Move { from: JsonPointer, path: JsonPointer },
+    Copy { from: JsonPointer, path: JsonPointer },
+    Test { path: JsonPointer, value: Json },
+}
+
+// One step of the alignment of two arrays.
+#[derive(Clone, Copy, PartialEq)]
+enum Edit {
+    Keep,
+    Change,
+    Insert,
+    Delete,
+}
+
+impl JsonPatch {
+    pub fn new() -> JsonPatch
+    {
+        JsonPatch { operations: vec![] }
+    }
+
+    /// Reads a patch from its JSON text, see `from_json`.
+    pub fn parse(text: &str) -> Result<JsonPatch, Error>
+    {
+        JsonPatch::from_json(&Json::parse(text)?)
+    }
+
+    /// Reads a patch from an array of operation objects. Fails with
+    /// `Error::InvalidPatch` when an operation is unknown or lacks a member
+    /// it needs, and `Error::InvalidType` or `Error::InvalidPath` when a
+    /// member isn't what it should be. Unknown members are ignored.
+    pub fn from_json(json: &Json) -> Result<JsonPatch, Error>
+    {
+        let operations = match json {
+            Json::Array(value) => value,
+            _ => { return Err(Error::InvalidType("array", kind(json))); },
+        };
+
+        let operations = operations.iter().map(operation).collect::<Result<_, _>>()?;
+
+        Ok(JsonPatch { operations })
+    }
+
+    pub fn to_json(&self) -> Json
+    {
+        Json::Array(self.operations.iter().map(PatchOperation::to_json).collect())
+    }
+
+    /// Applies every operation to `json`, or none of them: when one fails,
+    /// `json` is left as it was and its error is returned. That's
+    /// `Error::InvalidPath` for a missing value or an invalid position, and
+    /// `Error::TestFailed` for a `test` operation finding another value.
+    ///
+    /// The operations are applied to a copy of `json`, replacing it once
+    /// they all succeed.
+    pub fn apply(&self, json: &mut Json) -> Result<(), Error>
+    {
+        let mut result = json.clone();
+
+        for operation in &self.operations {
+            operation.apply(&mut result)?;
+        }
+
+        *json = result;
+        Ok(())
+    }
+
+    /// A patch turning `from` into `to`. Objects are compared member by
+    /// member and arrays are aligned to keep the elements they have in
+    /// common, so changes deep inside a document give short patches.
+    pub fn diff(from: &Json, to: &Json) -> JsonPatch
+    {
+        let mut patch = JsonPatch::new();
+
+        diff(from, to, &mut JsonPointer::new(), &mut patch.operations);
+
+        patch
+    }
+}
+
+impl PatchOperation {
+    pub fn to_json(&self) -> Json
+    {
+        let mut object = HashMap::new();
+
+        let (name, path) = match self {
+            PatchOperation::Add { path, .. }     => ("add", path),
+            PatchOperation::Remove { path }      => ("remove", path),
+            PatchOperation::Replace { path, .. } => ("replace", path),
+            PatchOperation::Move { path, .. }    => ("move", path),
+            PatchOperation::Copy { path, .. }    => ("copy", path),
+            PatchOperation::Test { path, .. }    => ("test", path),
+        };
+
+        object.insert(String::from("op"), Json::from(name));
+        object.insert(String::from("path"), Json::from(path.to_string()));
+
+        match self {
+            PatchOperation::Add { value, .. } | PatchOperation::Replace { value, .. } | PatchOperation::Test { value, .. } => {
+                object.insert(String::from("value"), value.clone());
+            },
+            PatchOperation::Move { from, .. } | PatchOperation::Copy { from, .. } => {
+                object.insert(String::from("from"), Json::from(from.to_string()));
+            },
+            PatchOperation::Remove { .. } => {},
+        }
+
+        Json::Object(object)
+    }
+
+    fn apply(&self, json: &mut Json) -> Result<(), Error>
+    {
+        match self {
+            PatchOperation::Add { path, value } => {
+                path.insert(json, value.clone())?;
+            },
+            PatchOperation::Remove { path } => {
+                path.remove(json)?;
+            },
+            PatchOperation::Replace { path, value } => {
+                let target = path.get_mut(json).ok_or_else(|| Error::InvalidPath(path.to_string()))?;
+                *target = value.clone();
+            },
+            PatchOperation::Move { from, path } => {
+                // A value can't be moved into one of its own children.
+                if path.segments().len() > from.segments().len() && path.segments().starts_with(from.segments()) {
+                    return Err(Error::InvalidPath(path.to_string()));
+                }
+
+                let value = from.remove(json)?;
+                path.insert(json, value)?;
+            },
+            PatchOperation::Copy { from, path } => {
+                let value = from.get(json).cloned().ok_or_else(|| Error::InvalidPath(from.to_string()))?;
+                path.insert(json, value)?;
+            },
+            PatchOperation::Test { path, value } => {
+                if path.get(json) != Some(value) {
+                    return Err(Error::TestFailed(path.to_string()));
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+fn operation(json: &Json) -> Result<PatchOperation, Error>
+{
+    let object = match json {
+        Json::Object(value) => value,
+        _ => { return Err(Error::InvalidType("object", kind(json))); },
+    };
+
+    let member = |name: &str| -> Result<&Json, Error> {
+        object.get(name).ok_or_else(|| Error::InvalidPatch(format!("missing \"{}\"", name)))
+    };
+
+    let pointer = |name: &str| -> Result<JsonPointer, Error> {
+        match member(name)? {
+            Json::String(text) => JsonPointer::parse(text),
+            other => Err(Error::InvalidType("string", kind(other))),
+        }
+    };
+
+    let op = match member("op")? {
+        Json::String(op) => op,
+        other => { return Err(Error::InvalidType("string", kind(other))); },
+    };
+
+    Ok(match op.as_str() {
+        "add"     => PatchOperation::Add { path: pointer("path")?, value: member("value")?.clone() },
+        "remove"  => PatchOperation::Remove { path: pointer("path")? },
+        "replace" => PatchOperation::Replace { path: pointer("path")?, value: member("value")?.clone() },
+        "move"    => PatchOperation::Move { from: pointer("from")?, path: pointer("path")? },
+        "copy"    => PatchOperation::Copy { from: pointer("from")?, path: pointer("path")? },
+        "test"    => PatchOperation::Test { path: pointer("path")?, value: member("value")?.clone() },
+        _ => { return Err(Error::InvalidPatch(format!("unknown operation \"{}\"", op))); },
+    })
+}
+
+fn diff(from: &Json, to: &Json, path: &mut JsonPointer, operations: &mut Vec<PatchOperation>)
+{
+    match (from, to) {
+        _ if from == to => {},
+        (Json::Object(from), Json::Object(to)) => {
+            let mut removed = from.keys().filter(|key| !to.contains_key(*key)).collect::<Vec<_>>();
+            let mut kept    = from.keys().filter(|key| to.contains_key(*key)).collect::<Vec<_>>();
+            let mut added   = to.keys().filter(|key| !from.contains_key(*key)).collect::<Vec<_>>();
+
+            removed.sort();
+            kept.sort();
+            added.sort();
+
+            for key in removed {
+                operations.push(PatchOperation::Remove { path: path.clone().with(key.as_str()) });
+            }
+
+            for key in kept {
+                path.push(key.as_str());
+                diff(&from[key], &to[key], path, operations);
+                path.pop();
+            }
+
+            for key in added {
+                operations.push(PatchOperation::Add { path: path.clone().with(key.as_str()), value: to[key].clone() });
+            }
+        },
+        (Json::Array(from), Json::Array(to)) => { diff_arrays(from, to, path, operations); },
+        _ => {
+            operations.push(PatchOperation::Replace { path: path.clone(), value: to.clone() });
+        },
+    }
+}
+
+// Turns `from` into `to` with the fewest insertions, deletions and changes
+// of single elements, changed elements being diffed in turn.
+fn diff_arrays(from: &[Json], to: &[Json], path: &mut JsonPointer, operations: &mut Vec<PatchOperation>)
+{
+    let prefix = from.iter().zip(to).take_while(|(a, b)| a == b).count();
+    let suffix = from[prefix..].iter().rev().zip(to[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+
+    let middle_from = &from[prefix..from.len() - suffix];
+    let middle_to   = &to[prefix..to.len() - suffix];
+
+    let mut index = prefix;
+
+    for edit in align(middle_from, middle_to) {
+        match edit {
+            (Edit::Keep, _, _) => { index += 1; },
+            (Edit::Change, i, j) => {
+                path.push(index.to_string());
+                diff(&middle_from[i], &middle_to[j], path, operations);
+                path.pop();
+                index += 1;
+            },
+            (Edit::Insert, _, j) => {
+                operations.push(PatchOperation::Add { path: path.clone().with(index.to_string()), value: middle_to[j].clone() });
+                index += 1;
+            },
+            (Edit::Delete, _, _) => {
+                operations.push(PatchOperation::Remove { path: path.clone().with(index.to_string()) });
+            },
+        }
+    }
+}
+
+// The edits turning `from` into `to`, with the indices of the elements they
+// concern in each, following the edit distance between them.
+fn align(from: &[Json], to: &[Json]) -> Vec<(Edit, usize, usize)>
+{
+    let (rows, columns) = (from.len() + 1, to.len() + 1);
+
+    if rows.saturating_mul(columns) > MAX_ALIGNMENT {
+        return naive_alignment(from, to);
+    }
+
+    // Distances between every prefix of `from` and of `to`.
+    let mut distance = vec![0_usize; rows * columns];
+
+    for i in 0..rows {
+        for j in 0..columns {
+            distance[i * columns + j] = match (i, j) {
+                (0, _) => j,
+                (_, 0) => i,
+                _ => {
+                    let change = if from[i - 1] == to[j - 1] { 0 } else { 1 };
+
+                    (distance[(i - 1) * columns + j - 1] + change)
+                        .min(distance[(i - 1) * columns + j] + 1)
+                        .min(distance[i * columns + j - 1] + 1)
+                },
+            };
+        }
+    }
+
+    let mut edits  = vec![];
+    let (mut i, mut j) = (from.len(), to.len());
+
+    while i > 0 || j > 0 {
+        let current = distance[i * columns + j];
+
+        if i > 0 && j > 0 {
+            let equal = from[i - 1] == to[j - 1];
+            let diagonal = distance[(i - 1) * columns + j - 1];
+
+            if current == diagonal + if equal { 0 } else { 1 } {
+                edits.push((if equal { Edit::Keep } else { Edit::Change }, i - 1, j - 1));
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+
+        if i > 0 && current == distance[(i - 1) * columns + j] + 1 {
+            edits.push((Edit::Delete, i - 1, j));
+            i -= 1;
+        } else {
+            edits.push((Edit::Insert, i, j - 1));
+            j -= 1;
+        }
+    }
+
+    edits.reverse();
+    edits
+}
+
+// Changes elements at the same position, then deletes or inserts the rest.
+fn naive_alignment(from: &[Json], to: &[Json]) -> Vec<(Edit, usize, usize)>
+{
+    let common    = from.len().min(to.len());
+    let mut edits = (0..common).map(|i| (Edit::Change, i, i)).collect::<Vec<_>>();
+
+    edits.extend((common..from.len()).map(|i| (Edit::Delete, i, common)));
+    edits.extend((common..to.len()).map(|j| (Edit::Insert, common, j)));
+
+    edits
+}
@@ -0,0 +1,282 @@
+use std::borrow::Cow;
+
+use crate::json::Json;
+use crate::number::Number;
+
+use super::regex::Regex;
+use super::{ Argument, Comparable, Comparison, Function, FunctionKind, Logical, Query, QueryMatch, Segment, Selector, Step };
+
+// The document and the value `@` stands for while evaluating a filter.
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    root:    &'a Json,
+    current: &'a Json,
+}
+
+pub fn query<'a>(segments: &[Segment], root: &'a Json) -> Vec<QueryMatch<'a>>
+{
+    let start = QueryMatch { value: root, location: vec![] };
+
+    select(segments, vec![start], root)
+}
+
+fn select<'a>(segments: &[Segment], mut nodes: Vec<QueryMatch<'a>>, root: &'a Json) -> Vec<QueryMatch<'a>>
+{
+    for segment in segments {
+        let mut output = vec![];
+
+        for node in &nodes {
+            if segment.descendant {
+                descend(node, segment, root, &mut output);
+            } else {
+                for selector in &segment.selectors {
+                    apply(selector, node, root, &mut output);
+                }
+            }
+        }
+
+        nodes = output;
+    }
+
+    nodes
+}
+
+// Applies the selectors of `segment` to `node` and to each of its
+// descendants, parents before their children.
+fn descend<'a>(node: &QueryMatch<'a>, segment: &Segment, root: &'a Json, output: &mut Vec<QueryMatch<'a>>)
+{
+    for selector in &segment.selectors {
+        apply(selector, node, root, output);
+    }
+
+    for child in children(node) {
+        descend(&child, segment, root, output);
+    }
+}
+
+fn children<'a>(node: &QueryMatch<'a>) -> Vec<QueryMatch<'a>>
+{
+    match node.value {
+        Json::Array(ref value) => {
+            value.iter().enumerate().map(|(index, value)| child(node, Step::Index(index), value)).collect()
+        },
+        Json::Object(ref value) => {
+            value.iter().map(|(key, value)| child(node, Step::Name(key), value)).collect()
+        },
+        _ => vec![],
+    }
+}
+
+fn child<'a>(node: &QueryMatch<'a>, step: Step<'a>, value: &'a Json) -> QueryMatch<'a>
+{
+    let mut location = node.location.clone();
+    location.push(step);
+
+    QueryMatch { value, location }
+}
+
+fn apply<'a>(selector: &Selector, node: &QueryMatch<'a>, root: &'a Json, output: &mut Vec<QueryMatch<'a>>)
+{
+    match (selector, node.value) {
+        (Selector::Name(name), Json::Object(ref value)) => {
+            if let Some((key, value)) = value.get_key_value(name) {
+                output.push(child(node, Step::Name(key), value));
+            }
+        },
+        (Selector::Wildcard, _) => { output.extend(children(node)); },
+        (Selector::Index(index), Json::Array(ref value)) => {
+            let len   = value.len() as i64;
+            let index = if *index < 0 { len + index } else { *index };
+
+            if (0..len).contains(&index) {
+                output.push(child(node, Step::Index(index as usize), &value[index as usize]));
+            }
+        },
+        (Selector::Slice(start, end, step), Json::Array(ref value)) => {
+            for index in slice(value.len() as i64, *start, *end, step.unwrap_or(1)) {
+                output.push(child(node, Step::Index(index), &value[index]));
+            }
+        },
+        (Selector::Filter(ref filter), _) => {
+            for candidate in children(node) {
+                let context = Context { root, current: candidate.value };
+
+                if test(filter, context) {
+                    output.push(candidate);
+                }
+            }
+        },
+        _ => {},
+    }
+}
+
+// Indices selected by a slice, following section 2.3.4.2.2 of RFC 9535.
+fn slice(len: i64, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize>
+{
+    let normalize = |index: i64| if index < 0 { len + index } else { index };
+    let mut indices = vec![];
+
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).max(0).min(len);
+        let upper = normalize(end.unwrap_or(len)).max(0).min(len);
+        let mut index = lower;
+
+        while index < upper {
+            indices.push(index as usize);
+            index += step;
+        }
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(len - 1)).max(-1).min(len - 1);
+        let lower = normalize(end.unwrap_or(-len - 1)).max(-1).min(len - 1);
+        let mut index = upper;
+
+        while lower < index {
+            indices.push(index as usize);
+            index += step;
+        }
+    }
+
+    indices
+}
+
+fn test(logical: &Logical, context: Context) -> bool
+{
+    match logical {
+        Logical::Or(operands)  => operands.iter().any(|operand| test(operand, context)),
+        Logical::And(operands) => operands.iter().all(|operand| test(operand, context)),
+        Logical::Not(operand)  => !test(operand, context),
+        Logical::Compare(left, comparison, right) => {
+            compare(&comparable(left, context), *comparison, &comparable(right, context))
+        },
+        Logical::Exists(query) => !nodes(query, context).is_empty(),
+        Logical::Test(function) => match function.kind {
+            FunctionKind::Match | FunctionKind::Search => matches(function, context),
+            _ => unreachable!(),
+        },
+    }
+}
+
+fn nodes<'a>(query: &Query, context: Context<'a>) -> Vec<&'a Json>
+{
+    let start = if query.relative { context.current } else { context.root };
+    let nodes = select(&query.segments, vec![QueryMatch { value: start, location: vec![] }], context.root);
+
+    nodes.into_iter().map(|node| node.value).collect()
+}
+
+// A single value, or `None` for the absence of one, which RFC 9535 calls
+// Nothing.
+fn comparable<'a>(comparable: &'a Comparable, context: Context<'a>) -> Option<Cow<'a, Json>>
+{
+    match comparable {
+        Comparable::Literal(value)    => Some(Cow::Borrowed(value)),
+        Comparable::Query(query)      => nodes(query, context).pop().map(Cow::Borrowed),
+        Comparable::Function(function) => value(function, context),
+    }
+}
+
+fn compare(left: &Option<Cow<Json>>, comparison: Comparison, right: &Option<Cow<Json>>) -> bool
+{
+    match comparison {
+        Comparison::Equal          => left == right,
+        Comparison::NotEqual       => left != right,
+        Comparison::Less           => less(left, right),
+        Comparison::LessOrEqual    => less(left, right) || left == right,
+        Comparison::Greater        => less(right, left),
+        Comparison::GreaterOrEqual => less(right, left) || left == right,
+    }
+}
+
+// Only numbers and strings are ordered, other values are neither less nor
+// greater than anything.
+fn less(left: &Option<Cow<Json>>, right: &Option<Cow<Json>>) -> bool
+{
+    match (left.as_deref(), right.as_deref()) {
+        (Some(Json::Number(a)), Some(Json::Number(b))) => a < b,
+        (Some(Json::String(a)), Some(Json::String(b))) => a < b,
+        _ => false,
+    }
+}
+
+// The result of a function returning a single value.
+fn value<'a>(function: &'a Function, context: Context<'a>) -> Option<Cow<'a, Json>>
+{
+    let argument = &function.arguments[0];
+
+    match function.kind {
+        FunctionKind::Length => {
+            let len = match argument_value(argument, context)?.as_ref() {
+                Json::String(value) => value.chars().count(),
+                Json::Array(value)  => value.len(),
+                Json::Object(value) => value.len(),
+                _ => { return None; },
+            };
+
+            Some(Cow::Owned(Json::Number(Number::Unsigned(len as u64))))
+        },
+        FunctionKind::Count => {
+            let count = argument_nodes(argument, context).len();
+
+            Some(Cow::Owned(Json::Number(Number::Unsigned(count as u64))))
+        },
+        FunctionKind::Value => {
+            let mut nodes = argument_nodes(argument, context);
+
+            match nodes.len() {
+                1 => nodes.pop().map(Cow::Borrowed),
+                _ => None,
+            }
+        },
+        FunctionKind::Match | FunctionKind::Search => unreachable!(),
+    }
+}
+
+fn matches(function: &Function, context: Context) -> bool
+{
+    let text = argument_value(&function.arguments[0], context);
+    let text = match text.as_deref() {
+        Some(Json::String(text)) => text,
+        _ => { return false; },
+    };
+
+    let compiled;
+    let regex = match function.regex {
+        Some(ref regex) => regex,
+        None => {
+            let pattern = argument_value(&function.arguments[1], context);
+
+            compiled = match pattern.as_deref() {
+                Some(Json::String(pattern)) => Regex::new(pattern),
+                _ => None,
+            };
+
+            match compiled {
+                Some(ref regex) => regex,
+                None => { return false; },
+            }
+        },
+    };
+
+    match function.kind {
+        FunctionKind::Match => regex.matches(text),
+        _ => regex.search(text),
+    }
+}
+
+fn argument_value<'a>(argument: &'a Argument, context: Context<'a>) -> Option<Cow<'a, Json>>
+{
+    match argument {
+        Argument::Literal(value)     => Some(Cow::Borrowed(value)),
+        Argument::Query(query)       => nodes(query, context).pop().map(Cow::Borrowed),
+        Argument::Function(function) => value(function, context),
+        Argument::Logical(_)         => None,
+    }
+}
+
+fn argument_nodes<'a>(argument: &'a Argument, context: Context<'a>) -> Vec<&'a Json>
+{
+    match argument {
+        Argument::Query(query) => nodes(query, context),
+        _ => vec![],
+    }
+}
@@ -0,0 +1,237 @@
+mod eval;
+mod parser;
+mod regex;
+
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::json::Json;
+use crate::pointer::JsonPointer;
+
+use self::regex::Regex;
+
+/// A JSONPath query as defined by RFC 9535, like
+/// `$.store.book[?@.price < 10].title`.
+///
+/// Queries are checked when parsed, well-typedness of function calls
+/// included, so running one can't fail: values it doesn't apply to are
+/// simply not selected. The standard functions `length`, `count`, `match`,
+/// `search` and `value` are available, regular expressions following
+/// I-Regexp (RFC 9485).
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+/// A value selected by a query, and where it was found.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryMatch<'a> {
+    pub value: &'a Json,
+    location:  Vec<Step<'a>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Step<'a> {
+    Name(&'a str),
+    Index(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Segment {
+    // Whether the selectors apply to all descendants, as with `..`.
+    descendant: bool,
+    selectors:  Vec<Selector>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    Filter(Logical),
+}
+
+// A query inside a filter, starting from `@` when relative, `$` otherwise.
+#[derive(Clone, Debug, PartialEq)]
+struct Query {
+    relative: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Logical {
+    Or(Vec<Logical>),
+    And(Vec<Logical>),
+    Not(Box<Logical>),
+    Compare(Comparable, Comparison, Comparable),
+    // True when the query selects anything.
+    Exists(Query),
+    // A function with a logical or nodes result.
+    Test(Function),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Comparable {
+    Literal(Json),
+    // Always a singular query.
+    Query(Query),
+    Function(Function),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Function {
+    kind:      FunctionKind,
+    arguments: Vec<Argument>,
+    // The pattern of `match` and `search`, compiled in advance when given as
+    // a literal.
+    regex:     Option<Regex>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FunctionKind {
+    Length,
+    Count,
+    Match,
+    Search,
+    Value,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Argument {
+    Literal(Json),
+    Query(Query),
+    Logical(Logical),
+    Function(Function),
+}
+
+// The types of function parameters and results in RFC 9535.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Type {
+    Value,
+    Logical,
+    Nodes,
+}
+
+impl FunctionKind {
+    fn parameters(self) -> &'static [Type]
+    {
+        match self {
+            FunctionKind::Length => &[Type::Value],
+            FunctionKind::Count  => &[Type::Nodes],
+            FunctionKind::Match  => &[Type::Value, Type::Value],
+            FunctionKind::Search => &[Type::Value, Type::Value],
+            FunctionKind::Value  => &[Type::Nodes],
+        }
+    }
+
+    fn result(self) -> Type
+    {
+        match self {
+            FunctionKind::Match | FunctionKind::Search => Type::Logical,
+            _ => Type::Value,
+        }
+    }
+}
+
+impl JsonPath {
+    /// Reads a query, failing with `Error::Syntax` at the offending
+    /// character. The error inside is `Error::InvalidType` for a function
+    /// argument or result of the wrong type, `Error::InvalidLength` for a
+    /// wrong number of arguments and `Error::OutOfRange` for an index beyond
+    /// what I-JSON allows.
+    pub fn parse(text: &str) -> Result<JsonPath, Error>
+    {
+        parser::parse(text)
+    }
+
+    /// The values `json` selects, in order, with their locations.
+    pub fn query<'a>(&self, json: &'a Json) -> Vec<QueryMatch<'a>>
+    {
+        eval::query(&self.segments, json)
+    }
+
+    /// Same as `query`, without the locations.
+    pub fn select<'a>(&self, json: &'a Json) -> Vec<&'a Json>
+    {
+        self.query(json).into_iter().map(|found| found.value).collect()
+    }
+}
+
+impl FromStr for JsonPath
+{
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<JsonPath, Error>
+    {
+        JsonPath::parse(text)
+    }
+}
+
+impl<'a> QueryMatch<'a> {
+    /// The normalized path of the value, like `$['store']['book'][0]`.
+    pub fn path(&self) -> String
+    {
+        let mut path = String::from("$");
+
+        for step in &self.location {
+            match step {
+                Step::Name(name)   => { write_name(&mut path, name); },
+                Step::Index(index) => { path.push_str(&format!("[{}]", index)); },
+            }
+        }
+
+        path
+    }
+
+    /// The location of the value as a JSON Pointer.
+    pub fn pointer(&self) -> JsonPointer
+    {
+        self.location.iter()
+            .map(|step| match step {
+                Step::Name(name)   => String::from(*name),
+                Step::Index(index) => index.to_string(),
+            })
+            .collect()
+    }
+}
+
+// Writes a member name of a normalized path, escaped as RFC 9535 requires.
+fn write_name(path: &mut String, name: &str)
+{
+    path.push_str("['");
+
+    for chr in name.chars() {
+        match chr {
+            '\u{8}'  => { path.push_str("\\b"); },
+            '\u{c}'  => { path.push_str("\\f"); },
+            '\n'     => { path.push_str("\\n"); },
+            '\r'     => { path.push_str("\\r"); },
+            '\t'     => { path.push_str("\\t"); },
+            '\''     => { path.push_str("\\'"); },
+            '\\'     => { path.push_str("\\\\"); },
+            '\u{0}'..='\u{1f}' => { path.push_str(&format!("\\u{:04x}", chr as u32)); },
+            _ => { path.push(chr); },
+        }
+    }
+
+    path.push_str("']");
+}
+
+impl Json {
+    /// The values selected by the JSONPath `query`, see `JsonPath`.
+    pub fn query(&self, query: &str) -> Result<Vec<&Json>, Error>
+    {
+        Ok(JsonPath::parse(query)?.select(self))
+    }
+}
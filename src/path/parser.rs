@@ -0,0 +1,684 @@
+use crate::error::{ Error, Position };
+use crate::json::Json;
+use crate::parser::number;
+
+use super::regex::Regex;
+use super::{ Argument, Comparable, Comparison, Function, FunctionKind, JsonPath, Logical, Query, Segment, Selector, Type };
+
+// Indices and slice bounds must be exact in a double, as with I-JSON.
+const MAX_INDEX: i64 = (1 << 53) - 1;
+
+// A filter expression before it's known whether it has to be a logical one,
+// as function arguments don't need to be.
+enum Expr {
+    Logical(Logical),
+    // With where it starts, for errors.
+    Operand(usize, Operand),
+}
+
+enum Operand {
+    Literal(Json),
+    Query(Query),
+    Function(Function),
+}
+
+pub fn parse(text: &str) -> Result<JsonPath, Error>
+{
+    let mut parser = Parser { text, chars: text.char_indices().collect(), index: 0 };
+
+    parser.expect('$')?;
+
+    let segments = parser.segments()?;
+
+    match parser.peek() {
+        Some(chr) => Err(parser.error(parser.index, Error::InvalidCharacter(chr.to_string()))),
+        None      => Ok(JsonPath { segments }),
+    }
+}
+
+struct Parser<'a> {
+    text:  &'a str,
+    chars: Vec<(usize, char)>,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, index: usize, error: Error) -> Error
+    {
+        let offset = self.chars.get(index).map_or(self.text.len(), |(offset, _)| *offset);
+        let before = &self.text[..offset];
+        let line   = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+
+        Error::Syntax(Position { line, column, offset }, Box::new(error))
+    }
+
+    // An error for the character at the current position.
+    fn unexpected(&self) -> Error
+    {
+        match self.peek() {
+            Some(chr) => self.error(self.index, Error::InvalidCharacter(chr.to_string())),
+            None      => self.error(self.index, Error::UnexpectedEof),
+        }
+    }
+
+    fn peek(&self) -> Option<char>
+    {
+        self.chars.get(self.index).map(|(_, chr)| *chr)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<char>
+    {
+        self.chars.get(self.index + ahead).map(|(_, chr)| *chr)
+    }
+
+    fn next(&mut self) -> Option<char>
+    {
+        let current = self.peek();
+
+        if current.is_some() {
+            self.index += 1;
+        }
+
+        current
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error>
+    {
+        if self.peek() != Some(expected) {
+            return Err(self.unexpected());
+        }
+
+        self.index += 1;
+        Ok(())
+    }
+
+    // Consumes `token` if it comes next.
+    fn accept(&mut self, token: &str) -> bool
+    {
+        let matches = token.chars().enumerate().all(|(i, chr)| self.peek_at(i) == Some(chr));
+
+        if matches {
+            self.index += token.chars().count();
+        }
+
+        matches
+    }
+
+    fn skip_blank(&mut self)
+    {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+            self.index += 1;
+        }
+    }
+
+    fn segments(&mut self) -> Result<Vec<Segment>, Error>
+    {
+        let mut segments = vec![];
+
+        loop {
+            // Blank space may separate segments, but mustn't be consumed
+            // when it's followed by something else.
+            let start = self.index;
+            self.skip_blank();
+
+            match self.peek() {
+                Some('[') => { segments.push(Segment { descendant: false, selectors: self.bracketed()? }); },
+                Some('.') => { segments.push(self.dotted()?); },
+                _ => {
+                    self.index = start;
+                    return Ok(segments);
+                },
+            }
+        }
+    }
+
+    fn dotted(&mut self) -> Result<Segment, Error>
+    {
+        self.expect('.')?;
+
+        let descendant = self.peek() == Some('.');
+
+        if descendant {
+            self.index += 1;
+        }
+
+        let selectors = match self.peek() {
+            Some('[') if descendant => self.bracketed()?,
+            Some('*') => {
+                self.index += 1;
+                vec![Selector::Wildcard]
+            },
+            Some(chr) if is_name_first(chr) => {
+                let mut name = String::new();
+
+                while let Some(chr) = self.peek().filter(|chr| is_name_first(*chr) || chr.is_ascii_digit()) {
+                    name.push(chr);
+                    self.index += 1;
+                }
+
+                vec![Selector::Name(name)]
+            },
+            _ => { return Err(self.unexpected()); },
+        };
+
+        Ok(Segment { descendant, selectors })
+    }
+
+    fn bracketed(&mut self) -> Result<Vec<Selector>, Error>
+    {
+        self.expect('[')?;
+
+        let mut selectors = vec![];
+
+        loop {
+            self.skip_blank();
+            selectors.push(self.selector()?);
+            self.skip_blank();
+
+            match self.peek() {
+                Some(',') => { self.index += 1; },
+                Some(']') => {
+                    self.index += 1;
+                    return Ok(selectors);
+                },
+                _ => { return Err(self.unexpected()); },
+            }
+        }
+    }
+
+    fn selector(&mut self) -> Result<Selector, Error>
+    {
+        match self.peek() {
+            Some('\'') | Some('"') => Ok(Selector::Name(self.string()?)),
+            Some('*') => {
+                self.index += 1;
+                Ok(Selector::Wildcard)
+            },
+            Some('?') => {
+                self.index += 1;
+                self.skip_blank();
+
+                let expr = self.or_expr()?;
+
+                Ok(Selector::Filter(self.logical(expr)?))
+            },
+            Some('-') | Some('0'..='9') | Some(':') => self.index_or_slice(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn index_or_slice(&mut self) -> Result<Selector, Error>
+    {
+        let start = self.optional_integer()?;
+        self.skip_blank();
+
+        if !self.accept(":") {
+            return match start {
+                Some(index) => Ok(Selector::Index(index)),
+                None        => Err(self.unexpected()),
+            };
+        }
+
+        self.skip_blank();
+        let end = self.optional_integer()?;
+        self.skip_blank();
+
+        let mut step = None;
+
+        if self.accept(":") {
+            self.skip_blank();
+            step = self.optional_integer()?;
+        }
+
+        Ok(Selector::Slice(start, end, step))
+    }
+
+    // An integer without leading zeros nor `-0`, when one comes next.
+    fn optional_integer(&mut self) -> Result<Option<i64>, Error>
+    {
+        let start = self.index;
+
+        match (self.peek(), self.peek_at(1)) {
+            (Some('0'), _) => {
+                self.index += 1;
+
+                if let Some('0'..='9') = self.peek() {
+                    return Err(self.unexpected());
+                }
+
+                return Ok(Some(0));
+            },
+            (Some('-'), Some('1'..='9')) | (Some('1'..='9'), _) => {},
+            (Some('-'), _) => {
+                self.index += 1;
+                return Err(self.unexpected());
+            },
+            _ => { return Ok(None); },
+        }
+
+        self.accept("-");
+
+        while let Some('0'..='9') = self.peek() {
+            self.index += 1;
+        }
+
+        let token: String = self.chars[start..self.index].iter().map(|(_, chr)| chr).collect();
+
+        match token.parse::<i64>() {
+            Ok(value) if (-MAX_INDEX..=MAX_INDEX).contains(&value) => Ok(Some(value)),
+            _ => Err(self.error(start, Error::OutOfRange(token, "index"))),
+        }
+    }
+
+    // A quoted string, with the escapes RFC 9535 allows.
+    fn string(&mut self) -> Result<String, Error>
+    {
+        let quote      = self.next();
+        let mut result = String::new();
+
+        loop {
+            match self.next() {
+                Some('\\') => {
+                    let chr = match self.next() {
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('/') => '/',
+                        Some('\\') => '\\',
+                        Some('u') => self.unicode_escape()?,
+                        chr if chr == quote => quote.unwrap(),
+                        Some(_) => {
+                            self.index -= 1;
+                            return Err(self.unexpected());
+                        },
+                        None => { return Err(self.unexpected()); },
+                    };
+
+                    result.push(chr);
+                },
+                chr if chr == quote => { return Ok(result); },
+                Some(chr) if chr >= ' ' => { result.push(chr); },
+                Some(_) => {
+                    self.index -= 1;
+                    return Err(self.unexpected());
+                },
+                None => { return Err(self.unexpected()); },
+            }
+        }
+    }
+
+    // The code point of a `\u` escape, or of a surrogate pair of them.
+    fn unicode_escape(&mut self) -> Result<char, Error>
+    {
+        let start = self.index;
+        let high  = self.hex()?;
+
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if !self.accept("\\u") {
+                    return Err(self.unexpected());
+                }
+
+                match self.hex()? {
+                    low @ 0xDC00..=0xDFFF => 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                    _ => { return Err(self.error(start, Error::InvalidCharacter(format!("\\u{:04X}", high)))); },
+                }
+            },
+            _ => high,
+        };
+
+        std::char::from_u32(code).ok_or_else(|| self.error(start, Error::InvalidCharacter(format!("\\u{:04X}", code))))
+    }
+
+    fn hex(&mut self) -> Result<u32, Error>
+    {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            match self.peek().and_then(|chr| chr.to_digit(16)) {
+                Some(digit) => {
+                    code   = code * 16 + digit;
+                    self.index += 1;
+                },
+                None => { return Err(self.unexpected()); },
+            }
+        }
+
+        Ok(code)
+    }
+
+    fn or_expr(&mut self) -> Result<Expr, Error>
+    {
+        let first = self.and_expr()?;
+
+        if !self.followed_by("||") {
+            return Ok(first);
+        }
+
+        let mut operands = vec![self.logical(first)?];
+
+        while self.followed_by("||") {
+            self.accept("||");
+            self.skip_blank();
+
+            let operand = self.and_expr()?;
+            operands.push(self.logical(operand)?);
+        }
+
+        Ok(Expr::Logical(Logical::Or(operands)))
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, Error>
+    {
+        let first = self.basic_expr()?;
+
+        if !self.followed_by("&&") {
+            return Ok(first);
+        }
+
+        let mut operands = vec![self.logical(first)?];
+
+        while self.followed_by("&&") {
+            self.accept("&&");
+            self.skip_blank();
+
+            let operand = self.basic_expr()?;
+            operands.push(self.logical(operand)?);
+        }
+
+        Ok(Expr::Logical(Logical::And(operands)))
+    }
+
+    // Whether `token` comes after blank space, which is then skipped. The
+    // position is left alone otherwise.
+    fn followed_by(&mut self, token: &str) -> bool
+    {
+        let start = self.index;
+        self.skip_blank();
+
+        let found = token.chars().enumerate().all(|(i, chr)| self.peek_at(i) == Some(chr));
+
+        if !found {
+            self.index = start;
+        }
+
+        found
+    }
+
+    fn basic_expr(&mut self) -> Result<Expr, Error>
+    {
+        match self.peek() {
+            Some('!') => {
+                self.index += 1;
+                self.skip_blank();
+
+                let operand = match self.peek() {
+                    Some('(') => self.parenthesized()?,
+                    _ => {
+                        let start   = self.index;
+                        let operand = self.operand()?;
+
+                        self.test(start, operand)?
+                    },
+                };
+
+                Ok(Expr::Logical(Logical::Not(Box::new(operand))))
+            },
+            Some('(') => Ok(Expr::Logical(self.parenthesized()?)),
+            _ => {
+                let start = self.index;
+                let left  = self.operand()?;
+
+                let comparison = match self.comparison() {
+                    Some(comparison) => comparison,
+                    None             => { return Ok(Expr::Operand(start, left)); },
+                };
+
+                self.skip_blank();
+
+                let right_start = self.index;
+                let right       = self.operand()?;
+
+                Ok(Expr::Logical(Logical::Compare(
+                    self.comparable(start, left)?,
+                    comparison,
+                    self.comparable(right_start, right)?,
+                )))
+            },
+        }
+    }
+
+    fn parenthesized(&mut self) -> Result<Logical, Error>
+    {
+        self.expect('(')?;
+        self.skip_blank();
+
+        let expr    = self.or_expr()?;
+        let logical = self.logical(expr)?;
+
+        self.skip_blank();
+        self.expect(')')?;
+
+        Ok(logical)
+    }
+
+    fn comparison(&mut self) -> Option<Comparison>
+    {
+        let operators = [
+            ("==", Comparison::Equal),
+            ("!=", Comparison::NotEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("<",  Comparison::Less),
+            (">",  Comparison::Greater),
+        ];
+
+        for (token, comparison) in operators.iter() {
+            if self.followed_by(token) {
+                self.accept(token);
+                return Some(*comparison);
+            }
+        }
+
+        None
+    }
+
+    fn operand(&mut self) -> Result<Operand, Error>
+    {
+        match self.peek() {
+            Some('@') | Some('$') => {
+                let relative = self.next() == Some('@');
+
+                Ok(Operand::Query(Query { relative, segments: self.segments()? }))
+            },
+            Some('\'') | Some('"') => Ok(Operand::Literal(Json::String(self.string()?))),
+            Some('-') | Some('0'..='9') => Ok(Operand::Literal(self.number()?)),
+            Some('a'..='z') => {
+                let start    = self.index;
+                let mut name = String::new();
+
+                while let Some(chr) = self.peek().filter(|chr| matches!(chr, 'a'..='z' | '0'..='9' | '_')) {
+                    name.push(chr);
+                    self.index += 1;
+                }
+
+                if self.peek() == Some('(') {
+                    return Ok(Operand::Function(self.function(start, &name)?));
+                }
+
+                match name.as_str() {
+                    "true"  => Ok(Operand::Literal(Json::Boolean(true))),
+                    "false" => Ok(Operand::Literal(Json::Boolean(false))),
+                    "null"  => Ok(Operand::Literal(Json::Null)),
+                    _ => Err(self.error(start, Error::InvalidCharacter(name))),
+                }
+            },
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, Error>
+    {
+        let start = self.index;
+
+        while let Some('0'..='9') | Some('.') | Some('e') | Some('E') | Some('+') | Some('-') = self.peek() {
+            self.index += 1;
+        }
+
+        let token: String = self.chars[start..self.index].iter().map(|(_, chr)| chr).collect();
+
+        let mut slice    = token.chars();
+        let mut peekable = (&mut slice).peekable();
+
+        match number(&mut peekable) {
+            Ok(value) if peekable.peek().is_none() => Ok(value),
+            _ => Err(self.error(start, Error::InvalidCharacter(token))),
+        }
+    }
+
+    fn function(&mut self, start: usize, name: &str) -> Result<Function, Error>
+    {
+        let kind = match name {
+            "length" => FunctionKind::Length,
+            "count"  => FunctionKind::Count,
+            "match"  => FunctionKind::Match,
+            "search" => FunctionKind::Search,
+            "value"  => FunctionKind::Value,
+            _ => { return Err(self.error(start, Error::InvalidCharacter(String::from(name)))); },
+        };
+
+        let parameters    = kind.parameters();
+        let mut arguments = vec![];
+
+        self.expect('(')?;
+        self.skip_blank();
+
+        if self.peek() != Some(')') {
+            loop {
+                let expr = self.or_expr()?;
+
+                if let Some(parameter) = parameters.get(arguments.len()) {
+                    arguments.push(self.argument(expr, *parameter)?);
+                } else {
+                    arguments.push(Argument::Literal(Json::Null));
+                }
+
+                self.skip_blank();
+
+                if !self.accept(",") {
+                    break;
+                }
+
+                self.skip_blank();
+            }
+        }
+
+        self.expect(')')?;
+
+        if arguments.len() != parameters.len() {
+            return Err(self.error(start, Error::InvalidLength(parameters.len(), arguments.len())));
+        }
+
+        let regex = match (kind, arguments.get(1)) {
+            (FunctionKind::Match, Some(Argument::Literal(Json::String(pattern)))) => Regex::new(pattern),
+            (FunctionKind::Search, Some(Argument::Literal(Json::String(pattern)))) => Regex::new(pattern),
+            _ => None,
+        };
+
+        Ok(Function { kind, arguments, regex })
+    }
+
+    fn argument(&self, expr: Expr, parameter: Type) -> Result<Argument, Error>
+    {
+        let (start, operand) = match expr {
+            Expr::Logical(logical) if parameter == Type::Logical => { return Ok(Argument::Logical(logical)); },
+            Expr::Logical(_) => {
+                return Err(self.error(self.index, Error::InvalidType(type_name(parameter), "logical")));
+            },
+            Expr::Operand(start, operand) => (start, operand),
+        };
+
+        let found = match (parameter, operand) {
+            (Type::Value, Operand::Literal(value)) => { return Ok(Argument::Literal(value)); },
+            (Type::Value, Operand::Query(query)) if is_singular(&query) => { return Ok(Argument::Query(query)); },
+            (Type::Nodes, Operand::Query(query)) => { return Ok(Argument::Query(query)); },
+            (Type::Logical, Operand::Query(query)) => { return Ok(Argument::Logical(Logical::Exists(query))); },
+            (_, Operand::Function(function)) => {
+                let result = function.kind.result();
+
+                if result == parameter || (parameter == Type::Logical && result == Type::Nodes) {
+                    return Ok(Argument::Function(function));
+                }
+
+                type_name(result)
+            },
+            (_, Operand::Literal(_)) => "value",
+            (_, Operand::Query(_))   => "nodes",
+        };
+
+        Err(self.error(start, Error::InvalidType(type_name(parameter), found)))
+    }
+
+    // Turns an expression into a logical one, for filters and operands of
+    // logical operators.
+    fn logical(&self, expr: Expr) -> Result<Logical, Error>
+    {
+        match expr {
+            Expr::Logical(logical)        => Ok(logical),
+            Expr::Operand(start, operand) => self.test(start, operand),
+        }
+    }
+
+    // An operand used on its own: queries test whether they select anything,
+    // functions must return a logical value or nodes.
+    fn test(&self, start: usize, operand: Operand) -> Result<Logical, Error>
+    {
+        match operand {
+            Operand::Query(query) => Ok(Logical::Exists(query)),
+            Operand::Function(function) if function.kind.result() != Type::Value => Ok(Logical::Test(function)),
+            Operand::Function(_) | Operand::Literal(_) => {
+                Err(self.error(start, Error::InvalidType("logical", "value")))
+            },
+        }
+    }
+
+    // Checks an operand of a comparison, which must be a single value.
+    fn comparable(&self, start: usize, operand: Operand) -> Result<Comparable, Error>
+    {
+        match operand {
+            Operand::Literal(value) => Ok(Comparable::Literal(value)),
+            Operand::Query(query) if is_singular(&query) => Ok(Comparable::Query(query)),
+            Operand::Query(_) => Err(self.error(start, Error::InvalidType("value", "nodes"))),
+            Operand::Function(function) => match function.kind.result() {
+                Type::Value => Ok(Comparable::Function(function)),
+                result      => Err(self.error(start, Error::InvalidType("value", type_name(result)))),
+            },
+        }
+    }
+}
+
+// Whether a query selects at most one value, only going through single
+// names and indices.
+fn is_singular(query: &Query) -> bool
+{
+    query.segments.iter().all(|segment| {
+        !segment.descendant && matches!(segment.selectors.as_slice(), [Selector::Name(_)] | [Selector::Index(_)])
+    })
+}
+
+fn is_name_first(chr: char) -> bool
+{
+    chr.is_ascii_alphabetic() || chr == '_' || chr >= '\u{80}'
+}
+
+fn type_name(kind: Type) -> &'static str
+{
+    match kind {
+        Type::Value   => "value",
+        Type::Logical => "logical",
+        Type::Nodes   => "nodes",
+    }
+}
@@ -0,0 +1,548 @@
+use std::mem;
+
+// Regular expressions following I-Regexp (RFC 9485), the interoperable
+// subset `match` and `search` take. Patterns are compiled into a program run
+// over all its possible states at once (a Pike VM), so matching takes time
+// linear in the text whatever the pattern.
+//
+// Unicode categories are exact up to U+00FF. Past that they are derived from
+// what the standard library exposes (alphabetic, numeric, case, whitespace)
+// and a few ranges for the common marks, punctuation and symbols, which is
+// enough for most scripts but not the full Unicode database.
+
+// Beyond this many instructions, or a count above it, repetitions are
+// considered abusive and the pattern invalid.
+const MAX_PROGRAM: usize = 10_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regex {
+    program: Vec<Instruction>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Instruction {
+    Char(Class),
+    // Continues at both places.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Class {
+    // Anything but line breaks, for `.`.
+    Any,
+    Literal(char),
+    // Matches when not negated and one of the items does, or the opposite.
+    Set(bool, Vec<Item>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Item {
+    Range(char, char),
+    // A category like `L` or `Lu`, complemented as with `\P` when negated.
+    Category(String, bool),
+}
+
+#[derive(Debug)]
+enum Node {
+    Empty,
+    Class(Class),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat(Box<Node>, usize, Option<usize>),
+}
+
+impl Regex {
+    /// Compiles `pattern`, or `None` when it isn't a valid I-Regexp.
+    pub fn new(pattern: &str) -> Option<Regex>
+    {
+        let mut parser = Parser { chars: pattern.chars().collect(), index: 0 };
+
+        let node = parser.alternate()?;
+
+        if parser.index < parser.chars.len() {
+            return None;
+        }
+
+        let mut program = vec![];
+        emit(&node, &mut program)?;
+        program.push(Instruction::Match);
+
+        Some(Regex { program })
+    }
+
+    /// Whether the whole of `text` matches.
+    pub fn matches(&self, text: &str) -> bool
+    {
+        self.run(text, true)
+    }
+
+    /// Whether some part of `text` matches.
+    pub fn search(&self, text: &str) -> bool
+    {
+        self.run(text, false)
+    }
+
+    fn run(&self, text: &str, anchored: bool) -> bool
+    {
+        let mut current    = vec![];
+        let mut next       = vec![];
+        let mut marks      = vec![0; self.program.len()];
+        let mut generation = 1;
+
+        self.add(0, &mut current, &mut marks, generation);
+
+        for chr in text.chars() {
+            if !anchored && self.matched(&current) {
+                return true;
+            }
+
+            generation += 1;
+            next.clear();
+
+            for pc in &current {
+                if let Instruction::Char(ref class) = self.program[*pc] {
+                    if class.contains(chr) {
+                        self.add(*pc + 1, &mut next, &mut marks, generation);
+                    }
+                }
+            }
+
+            // Searching starts a new attempt at every position.
+            if !anchored {
+                self.add(0, &mut next, &mut marks, generation);
+            }
+
+            mem::swap(&mut current, &mut next);
+
+            if current.is_empty() {
+                return false;
+            }
+        }
+
+        self.matched(&current)
+    }
+
+    fn matched(&self, threads: &[usize]) -> bool
+    {
+        threads.iter().any(|pc| self.program[*pc] == Instruction::Match)
+    }
+
+    // Adds the thread at `pc` to `threads`, following jumps and splits up to
+    // the instructions consuming a character. `marks` keeps each from being
+    // added twice in the same generation.
+    fn add(&self, pc: usize, threads: &mut Vec<usize>, marks: &mut [usize], generation: usize)
+    {
+        let mut stack = vec![pc];
+
+        while let Some(pc) = stack.pop() {
+            if marks[pc] == generation {
+                continue;
+            }
+
+            marks[pc] = generation;
+
+            match self.program[pc] {
+                Instruction::Jump(target) => { stack.push(target); },
+                Instruction::Split(first, second) => {
+                    stack.push(second);
+                    stack.push(first);
+                },
+                _ => { threads.push(pc); },
+            }
+        }
+    }
+}
+
+fn emit(node: &Node, program: &mut Vec<Instruction>) -> Option<()>
+{
+    if program.len() > MAX_PROGRAM {
+        return None;
+    }
+
+    match node {
+        Node::Empty => {},
+        Node::Class(class) => { program.push(Instruction::Char(class.clone())); },
+        Node::Concat(nodes) => {
+            for node in nodes {
+                emit(node, program)?;
+            }
+        },
+        Node::Alternate(branches) => {
+            let mut jumps = vec![];
+
+            for (i, branch) in branches.iter().enumerate() {
+                if i + 1 == branches.len() {
+                    emit(branch, program)?;
+                    break;
+                }
+
+                let split = program.len();
+                program.push(Instruction::Split(split + 1, 0));
+                emit(branch, program)?;
+
+                jumps.push(program.len());
+                program.push(Instruction::Jump(0));
+                program[split] = Instruction::Split(split + 1, program.len());
+            }
+
+            let end = program.len();
+
+            for jump in jumps {
+                program[jump] = Instruction::Jump(end);
+            }
+        },
+        Node::Repeat(node, min, max) => {
+            // Repeating what emits nothing changes nothing, and would
+            // otherwise never reach the size limit.
+            for _ in 0..*min {
+                let start = program.len();
+                emit(node, program)?;
+
+                if program.len() == start {
+                    break;
+                }
+            }
+
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Instruction::Split(split + 1, 0));
+                    emit(node, program)?;
+                    program.push(Instruction::Jump(split));
+                    program[split] = Instruction::Split(split + 1, program.len());
+                },
+                Some(max) => {
+                    let mut splits = vec![];
+
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Instruction::Split(0, 0));
+                        emit(node, program)?;
+                    }
+
+                    let end = program.len();
+
+                    for split in splits {
+                        program[split] = Instruction::Split(split + 1, end);
+                    }
+                },
+            }
+        },
+    }
+
+    Some(())
+}
+
+impl Class {
+    fn contains(&self, chr: char) -> bool
+    {
+        match self {
+            Class::Any => chr != '\n' && chr != '\r',
+            Class::Literal(literal) => chr == *literal,
+            Class::Set(negated, items) => items.iter().any(|item| item.contains(chr)) != *negated,
+        }
+    }
+}
+
+impl Item {
+    fn contains(&self, chr: char) -> bool
+    {
+        match self {
+            Item::Range(first, last) => (*first..=*last).contains(&chr),
+            Item::Category(name, negated) => category(chr).starts_with(name.as_str()) != *negated,
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char>
+    {
+        self.chars.get(self.index).cloned()
+    }
+
+    fn next(&mut self) -> Option<char>
+    {
+        let current = self.peek();
+        self.index += 1;
+        current
+    }
+
+    fn accept(&mut self, expected: char) -> bool
+    {
+        let found = self.peek() == Some(expected);
+
+        if found {
+            self.index += 1;
+        }
+
+        found
+    }
+
+    fn alternate(&mut self) -> Option<Node>
+    {
+        let mut branches = vec![self.branch()?];
+
+        while self.accept('|') {
+            branches.push(self.branch()?);
+        }
+
+        match branches.len() {
+            1 => branches.pop(),
+            _ => Some(Node::Alternate(branches)),
+        }
+    }
+
+    fn branch(&mut self) -> Option<Node>
+    {
+        let mut pieces = vec![];
+
+        while let Some(chr) = self.peek() {
+            if chr == '|' || chr == ')' {
+                break;
+            }
+
+            let atom = self.atom()?;
+            pieces.push(self.quantifier(atom)?);
+        }
+
+        match pieces.len() {
+            0 => Some(Node::Empty),
+            1 => pieces.pop(),
+            _ => Some(Node::Concat(pieces)),
+        }
+    }
+
+    fn atom(&mut self) -> Option<Node>
+    {
+        let class = match self.next()? {
+            '(' => {
+                let node = self.alternate()?;
+                return if self.accept(')') { Some(node) } else { None };
+            },
+            '.'  => Class::Any,
+            '['  => self.class_expression()?,
+            '\\' => self.escape()?,
+            ')' | '*' | '+' | '?' | ']' | '{' | '}' => { return None; },
+            chr => Class::Literal(chr),
+        };
+
+        Some(Node::Class(class))
+    }
+
+    fn quantifier(&mut self, atom: Node) -> Option<Node>
+    {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.index += 1;
+
+                let min = self.quantity()?;
+                let max = if self.accept(',') {
+                    if self.peek() == Some('}') { None } else { Some(self.quantity()?) }
+                } else {
+                    Some(min)
+                };
+
+                if self.peek() != Some('}') || max.is_some_and(|max| max < min) {
+                    return None;
+                }
+
+                (min, max)
+            },
+            _ => { return Some(atom); },
+        };
+
+        self.index += 1;
+        Some(Node::Repeat(Box::new(atom), min, max))
+    }
+
+    fn quantity(&mut self) -> Option<usize>
+    {
+        let start = self.index;
+
+        while let Some('0'..='9') = self.peek() {
+            self.index += 1;
+        }
+
+        let count = self.chars[start..self.index].iter().collect::<String>().parse().ok()?;
+
+        if count > MAX_PROGRAM { None } else { Some(count) }
+    }
+
+    // What follows a backslash, outside or inside brackets.
+    fn escape(&mut self) -> Option<Class>
+    {
+        match self.next()? {
+            'n' => Some(Class::Literal('\n')),
+            'r' => Some(Class::Literal('\r')),
+            't' => Some(Class::Literal('\t')),
+            chr @ '('..='+' | chr @ '-' | chr @ '.' | chr @ '?' | chr @ '['..='^' | chr @ '{'..='}' => {
+                Some(Class::Literal(chr))
+            },
+            chr @ 'p' | chr @ 'P' => {
+                let category = self.category()?;
+                Some(Class::Set(false, vec![Item::Category(category, chr == 'P')]))
+            },
+            _ => None,
+        }
+    }
+
+    // The `{Lu}` part of `\p{Lu}`.
+    fn category(&mut self) -> Option<String>
+    {
+        if !self.accept('{') {
+            return None;
+        }
+
+        let major = self.next()?;
+        let minor = if self.peek() == Some('}') { None } else { self.next() };
+
+        let minors = match major {
+            'L' => "lmotu",
+            'M' => "cen",
+            'N' => "dlo",
+            'P' => "cdefios",
+            'Z' => "lps",
+            'S' => "ckmo",
+            'C' => "cfnos",
+            _ => { return None; },
+        };
+
+        let valid = minor.map_or(true, |minor| minors.contains(minor));
+
+        if !valid || !self.accept('}') {
+            return None;
+        }
+
+        Some(major.to_string() + &minor.map(String::from).unwrap_or_default())
+    }
+
+    // The inside of brackets, past the `[`.
+    fn class_expression(&mut self) -> Option<Class>
+    {
+        let negated   = self.accept('^');
+        let mut items = vec![];
+
+        if self.accept('-') {
+            items.push(Item::Range('-', '-'));
+        }
+
+        loop {
+            match self.peek()? {
+                ']' if !items.is_empty() => {
+                    self.index += 1;
+                    return Some(Class::Set(negated, items));
+                },
+                '-' if self.chars.get(self.index + 1) == Some(&']') && !items.is_empty() => {
+                    self.index += 1;
+                    items.push(Item::Range('-', '-'));
+                },
+                '\\' if matches!(self.chars.get(self.index + 1), Some('p') | Some('P')) => {
+                    self.index += 1;
+
+                    match self.escape()? {
+                        Class::Set(_, mut category) => { items.append(&mut category); },
+                        _ => { return None; },
+                    }
+                },
+                _ => {
+                    let first = self.class_char()?;
+
+                    let last = if self.peek() == Some('-') && self.chars.get(self.index + 1) != Some(&']') {
+                        self.index += 1;
+                        self.class_char()?
+                    } else {
+                        first
+                    };
+
+                    if last < first {
+                        return None;
+                    }
+
+                    items.push(Item::Range(first, last));
+                },
+            }
+        }
+    }
+
+    fn class_char(&mut self) -> Option<char>
+    {
+        match self.next()? {
+            '\\' => match self.escape()? {
+                Class::Literal(chr) => Some(chr),
+                _ => None,
+            },
+            '-' | '[' | ']' => None,
+            chr => Some(chr),
+        }
+    }
+}
+
+// The Unicode general category of `chr`, as a two letter name.
+fn category(chr: char) -> &'static str
+{
+    match chr {
+        '\u{0}'..='\u{1f}' | '\u{7f}'..='\u{9f}' => "Cc",
+        ' ' | '\u{a0}' => "Zs",
+        '0'..='9' => "Nd",
+        'A'..='Z' | '\u{c0}'..='\u{d6}' | '\u{d8}'..='\u{de}' => "Lu",
+        'a'..='z' | '\u{b5}' | '\u{df}'..='\u{f6}' | '\u{f8}'..='\u{ff}' => "Ll",
+        '\u{aa}' | '\u{ba}' => "Lo",
+        '(' | '[' | '{' => "Ps",
+        ')' | ']' | '}' => "Pe",
+        '-' => "Pd",
+        '_' => "Pc",
+        '\u{ab}' => "Pi",
+        '\u{bb}' => "Pf",
+        '$' | '\u{a2}'..='\u{a5}' => "Sc",
+        '+' | '<' | '=' | '>' | '|' | '~' | '\u{ac}' | '\u{b1}' | '\u{d7}' | '\u{f7}' => "Sm",
+        '^' | '`' | '\u{a8}' | '\u{af}' | '\u{b4}' | '\u{b8}' => "Sk",
+        '\u{a6}' | '\u{a9}' | '\u{ae}' | '\u{b0}' => "So",
+        '\u{ad}' => "Cf",
+        '\u{b2}' | '\u{b3}' | '\u{b9}' | '\u{bc}'..='\u{be}' => "No",
+        // The rest of ASCII and Latin-1 is other punctuation.
+        '\u{0}'..='\u{ff}' => "Po",
+
+        '\u{2028}' => "Zl",
+        '\u{2029}' => "Zp",
+        _ if chr.is_whitespace() => "Zs",
+        '\u{600}'..='\u{605}' | '\u{61c}' | '\u{6dd}' | '\u{70f}' | '\u{200b}'..='\u{200f}' | '\u{202a}'..='\u{202e}'
+        | '\u{2060}'..='\u{2064}' | '\u{2066}'..='\u{206f}' | '\u{feff}' | '\u{fff9}'..='\u{fffb}' | '\u{e0001}'
+        | '\u{e0020}'..='\u{e007f}' => "Cf",
+        '\u{e000}'..='\u{f8ff}' | '\u{f0000}'..='\u{ffffd}' | '\u{100000}'..='\u{10fffd}' => "Co",
+        '\u{488}'..='\u{489}' | '\u{20dd}'..='\u{20e0}' | '\u{20e2}'..='\u{20e4}' => "Me",
+        '\u{300}'..='\u{36f}' | '\u{483}'..='\u{487}' | '\u{591}'..='\u{5bd}' | '\u{610}'..='\u{61a}'
+        | '\u{64b}'..='\u{65f}' | '\u{1ab0}'..='\u{1aff}' | '\u{1dc0}'..='\u{1dff}' | '\u{20d0}'..='\u{20ff}'
+        | '\u{fe00}'..='\u{fe0f}' | '\u{fe20}'..='\u{fe2f}' => "Mn",
+        '\u{2010}'..='\u{2015}' => "Pd",
+        '\u{2018}' | '\u{201b}' | '\u{201c}' | '\u{201f}' | '\u{2039}' => "Pi",
+        '\u{2019}' | '\u{201d}' | '\u{203a}' => "Pf",
+        '\u{201a}' | '\u{201e}' => "Ps",
+        '\u{3008}'..='\u{3011}' => match chr as u32 & 1 { 0 => "Ps", _ => "Pe" },
+        '\u{2016}' | '\u{2017}' | '\u{2020}'..='\u{2027}' | '\u{2030}'..='\u{2038}' | '\u{203b}'..='\u{203e}'
+        | '\u{3001}'..='\u{3003}' => "Po",
+        '\u{20a0}'..='\u{20c0}' => "Sc",
+        '\u{2190}'..='\u{2194}' | '\u{2200}'..='\u{22ff}' => "Sm",
+        '\u{2195}'..='\u{21ff}' | '\u{2300}'..='\u{23ff}' | '\u{2500}'..='\u{27bf}' | '\u{1f300}'..='\u{1faff}' => "So",
+        '\u{2160}'..='\u{2188}' | '\u{3007}' | '\u{3021}'..='\u{3029}' => "Nl",
+        '\u{660}'..='\u{669}' | '\u{6f0}'..='\u{6f9}' | '\u{7c0}'..='\u{7c9}' | '\u{966}'..='\u{96f}'
+        | '\u{9e6}'..='\u{9ef}' | '\u{a66}'..='\u{a6f}' | '\u{ae6}'..='\u{aef}' | '\u{b66}'..='\u{b6f}'
+        | '\u{be6}'..='\u{bef}' | '\u{c66}'..='\u{c6f}' | '\u{ce6}'..='\u{cef}' | '\u{d66}'..='\u{d6f}'
+        | '\u{e50}'..='\u{e59}' | '\u{ed0}'..='\u{ed9}' | '\u{f20}'..='\u{f29}' | '\u{1040}'..='\u{1049}'
+        | '\u{17e0}'..='\u{17e9}' | '\u{1810}'..='\u{1819}' | '\u{ff10}'..='\u{ff19}' => "Nd",
+        _ if chr.is_numeric()    => "No",
+        _ if chr.is_uppercase()  => "Lu",
+        _ if chr.is_lowercase()  => "Ll",
+        _ if chr.is_alphabetic() => "Lo",
+        _ => "Cn",
+    }
+}
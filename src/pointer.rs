@@ -0,0 +1,246 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::json::Json;
+
+/// A JSON Pointer as defined by RFC 6901, like `/servers/0/port`: the path
+/// to a value through the keys and positions leading to it. The empty
+/// pointer refers to the whole document.
+///
+/// Segments are kept unescaped, `~1` and `~0` only appear in the text form
+/// standing for `/` and `~`.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct JsonPointer {
+    segments: Vec<String>,
+}
+
+impl JsonPointer {
+    /// The empty pointer, referring to the whole document.
+    pub fn new() -> JsonPointer
+    {
+        JsonPointer { segments: vec![] }
+    }
+
+    /// Reads the text form of a pointer, which is either empty or made of
+    /// segments each starting with `/`. Fails with `Error::InvalidPath` when
+    /// it starts otherwise or uses `~` for anything but `~0` and `~1`.
+    pub fn parse(text: &str) -> Result<JsonPointer, Error>
+    {
+        if text.is_empty() {
+            return Ok(JsonPointer::new());
+        }
+
+        if !text.starts_with('/') {
+            return Err(Error::InvalidPath(String::from(text)));
+        }
+
+        let segments = text[1..].split('/')
+            .map(unescape)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| Error::InvalidPath(String::from(text)))?;
+
+        Ok(JsonPointer { segments })
+    }
+
+    /// Adds `segment` at the end, as is: it is escaped when displayed.
+    pub fn push<S: Into<String>>(&mut self, segment: S)
+    {
+        self.segments.push(segment.into());
+    }
+
+    /// Same as `push`, taking and returning the pointer to chain calls.
+    pub fn with<S: Into<String>>(mut self, segment: S) -> JsonPointer
+    {
+        self.push(segment);
+        self
+    }
+
+    pub fn pop(&mut self) -> Option<String>
+    {
+        self.segments.pop()
+    }
+
+    pub fn segments(&self) -> &[String]
+    {
+        &self.segments
+    }
+
+    pub fn is_root(&self) -> bool
+    {
+        self.segments.is_empty()
+    }
+
+    /// The value this pointer refers to in `json`, if any.
+    pub fn get<'a>(&self, json: &'a Json) -> Option<&'a Json>
+    {
+        self.segments.iter().try_fold(json, |json, segment| match json {
+            Json::Object(ref value) => value.get(segment),
+            Json::Array(ref value)  => value.get(array_index(segment)?),
+            _ => None,
+        })
+    }
+
+    pub fn get_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json>
+    {
+        self.segments.iter().try_fold(json, |json, segment| match json {
+            Json::Object(ref mut value) => value.get_mut(segment),
+            Json::Array(ref mut value)  => value.get_mut(array_index(segment)?),
+            _ => None,
+        })
+    }
+
+    /// Puts `value` where this pointer refers to in `json`, the way the `add`
+    /// operation of JSON Patch does: object members are added or replaced,
+    /// array elements are inserted before the one at that position, and `-`
+    /// appends to the array. The empty pointer replaces the whole document.
+    ///
+    /// Returns the object member or document replaced, if any. Fails with
+    /// `Error::InvalidPath` when the parent doesn't exist, isn't a container,
+    /// or the position is past the end of the array.
+    pub fn insert(&self, json: &mut Json, value: Json) -> Result<Option<Json>, Error>
+    {
+        let (last, parent) = match self.segments.split_last() {
+            Some((last, parent)) => (last, parent),
+            None => { return Ok(Some(mem::replace(json, value))); },
+        };
+
+        match self.parent(json, parent)? {
+            Json::Object(ref mut object) => Ok(object.insert(last.clone(), value)),
+            Json::Array(ref mut array) => {
+                let index = match last.as_str() {
+                    "-" => Some(array.len()),
+                    _   => array_index(last).filter(|index| *index <= array.len()),
+                };
+                let index = index.ok_or_else(|| self.invalid())?;
+
+                array.insert(index, value);
+                Ok(None)
+            },
+            _ => Err(self.invalid()),
+        }
+    }
+
+    /// Takes the value this pointer refers to out of `json`, removing the
+    /// object member or array element holding it. Fails with
+    /// `Error::InvalidPath` when there's no such value, or for the empty
+    /// pointer since a document can't be removed from itself.
+    pub fn remove(&self, json: &mut Json) -> Result<Json, Error>
+    {
+        let (last, parent) = self.segments.split_last().ok_or_else(|| self.invalid())?;
+
+        let removed = match self.parent(json, parent)? {
+            Json::Object(ref mut object) => object.remove(last),
+            Json::Array(ref mut array) => {
+                array_index(last).filter(|index| *index < array.len()).map(|index| array.remove(index))
+            },
+            _ => None,
+        };
+
+        removed.ok_or_else(|| self.invalid())
+    }
+
+    fn parent<'a>(&self, json: &'a mut Json, parent: &[String]) -> Result<&'a mut Json, Error>
+    {
+        let pointer = JsonPointer { segments: parent.to_vec() };
+
+        pointer.get_mut(json).ok_or_else(|| self.invalid())
+    }
+
+    fn invalid(&self) -> Error
+    {
+        Error::InvalidPath(self.to_string())
+    }
+}
+
+// Position of an element, written without leading zeros. `-` and anything
+// else that isn't a number refer to no element.
+fn array_index(segment: &str) -> Option<usize>
+{
+    if segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    if segment.len() > 1 && segment.starts_with('0') {
+        return None;
+    }
+
+    segment.parse().ok()
+}
+
+fn unescape(segment: &str) -> Option<String>
+{
+    let mut result = String::with_capacity(segment.len());
+    let mut chars  = segment.chars();
+
+    while let Some(chr) = chars.next() {
+        match chr {
+            '~' => match chars.next() {
+                Some('0') => { result.push('~'); },
+                Some('1') => { result.push('/'); },
+                _ => { return None; },
+            },
+            _ => { result.push(chr); },
+        }
+    }
+
+    Some(result)
+}
+
+impl fmt::Display for JsonPointer
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        for segment in &self.segments {
+            write!(f, "/{}", segment.replace('~', "~0").replace('/', "~1"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for JsonPointer
+{
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<JsonPointer, Error>
+    {
+        JsonPointer::parse(text)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for JsonPointer
+{
+    fn from_iter<I: IntoIterator<Item = S>>(segments: I) -> JsonPointer
+    {
+        JsonPointer { segments: segments.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl Json {
+    /// The value at `pointer`, written as in RFC 6901. `None` when there's no
+    /// such value, or when `pointer` isn't valid.
+    pub fn pointer(&self, pointer: &str) -> Option<&Json>
+    {
+        JsonPointer::parse(pointer).ok()?.get(self)
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Json>
+    {
+        JsonPointer::parse(pointer).ok()?.get_mut(self)
+    }
+
+    /// Puts `value` at `pointer`, see `JsonPointer::insert`.
+    pub fn pointer_insert(&mut self, pointer: &str, value: Json) -> Result<Option<Json>, Error>
+    {
+        JsonPointer::parse(pointer)?.insert(self, value)
+    }
+
+    /// Takes the value at `pointer` out, see `JsonPointer::remove`.
+    pub fn pointer_remove(&mut self, pointer: &str) -> Result<Json, Error>
+    {
+        JsonPointer::parse(pointer)?.remove(self)
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::fmt::{ self, Write };
+use std::io;
+
+use crate::color::{ paint, punctuation, Role };
+use crate::error::Error;
+use crate::escape::escape_into;
+use crate::format::{ Format, KeyOrder, NonFinite };
+use crate::json::Json;
+use crate::layout::{ fits, flat, Measure };
+use crate::number::Number;
+
+pub fn serialize<W: Write>(writer: &mut W, json: &Json, format: &Format, depth: usize) -> Result<(), Error>
+{
+    serialize_at(writer, json, format, depth, indent_width(format, depth))
+}
+
+// Same as `serialize`, for a value starting on `column` rather than right
+// after the indentation.
+pub fn serialize_at<W: Write>(writer: &mut W, json: &Json, format: &Format, depth: usize, column: usize) -> Result<(), Error>
+{
+    let room = format.max_width.unwrap_or(0).saturating_sub(column);
+
+    value(writer, json, format, depth, room)
+}
+
+// `room` is what is left of `max_width` on the current line once this value
+// and whatever must follow it on that line are written.
+fn value<W: Write>(writer: &mut W, json: &Json, format: &Format, depth: usize, room: usize) -> Result<(), Error>
+{
+    let grouped = format.indent.is_some() && format.max_width.is_some();
+
+    match json {
+        Json::Array(_) | Json::Object(_) if grouped && fits(json, format, room) => {
+            flat(writer, json, format)
+        },
+        Json::Array(ref value)  => array(writer, value, format, depth),
+        Json::Object(ref value) => object(writer, value, format, depth),
+        _ => scalar(writer, json, format),
+    }
+}
+
+// Writes to an io::Write through fmt::Write, keeping the actual io::Error
+// around since fmt::Error can't carry it.
+pub struct IoAdapter<'a, W: io::Write> {
+    inner:     &'a mut W,
+    pub error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write> IoAdapter<'a, W> {
+    pub fn new(inner: &'a mut W) -> IoAdapter<'a, W>
+    {
+        IoAdapter { inner, error: None }
+    }
+
+    // Prefers the io::Error that caused a formatting failure, if any.
+    pub fn result(self, result: Result<(), Error>) -> Result<(), Error>
+    {
+        match self.error {
+            Some(error) => Err(Error::from(error)),
+            None        => result,
+        }
+    }
+}
+
+impl<'a, W: io::Write> Write for IoAdapter<'a, W>
+{
+    fn write_str(&mut self, string: &str) -> fmt::Result
+    {
+        match self.inner.write_all(string.as_bytes()) {
+            Ok(())     => Ok(()),
+            Err(error) => {
+                self.error = Some(error);
+                Err(fmt::Error)
+            },
+        }
+    }
+}
+
+pub fn scalar<W: Write>(writer: &mut W, json: &Json, format: &Format) -> Result<(), Error>
+{
+    match json {
+        Json::Null => {
+            paint(writer, format, Role::Null, |w| Ok(w.write_str("null")?))?;
+        },
+        Json::Boolean(value) => {
+            paint(writer, format, Role::Boolean, |w| Ok(w.write_str(
+                if *value { "true"  }
+                else      { "false" }
+            )?))?;
+        },
+        Json::Number(Number::Float(value)) if !value.is_finite() => {
+            let literal = Number::Float(*value).to_string();
+
+            match format.non_finite {
+                NonFinite::Error   => { return Err(Error::NonFiniteNumber(literal)); },
+                NonFinite::Null    => { paint(writer, format, Role::Null, |w| Ok(w.write_str("null")?))?; },
+                NonFinite::String  => { paint(writer, format, Role::String, |w| Ok(write!(w, "\"{}\"", literal)?))?; },
+                NonFinite::Literal => { paint(writer, format, Role::Number, |w| Ok(w.write_str(&literal)?))?; },
+            }
+        },
+        Json::Number(ref value) => {
+            paint(writer, format, Role::Number, |w| Ok(write!(w, "{}", value)?))?;
+        },
+        Json::String(ref value) => {
+            paint(writer, format, Role::String, |w| Ok(escape_into(w, value, format)?))?;
+        },
+        _ => {},
+    }
+
+    Ok(())
+}
+
+pub fn write_key<W: Write>(writer: &mut W, key: &str, format: &Format) -> Result<(), Error>
+{
+    paint(writer, format, Role::Key, |w| Ok(escape_into(w, key, format)?))
+}
+
+fn array<W: Write>(writer: &mut W, value: &[Json], format: &Format, depth: usize) -> Result<(), Error>
+{
+    let indent = match format.indent {
+        Some(ref indent) if !value.is_empty() => indent,
+
+        // Compact output.
+        _ => {
+            punctuation(writer, format, "[")?;
+            for (i, elem) in value.iter().enumerate() {
+                if i > 0 {
+                    punctuation(writer, format, ",")?;
+                }
+                serialize(writer, elem, format, depth)?;
+            }
+            punctuation(writer, format, "]")?;
+
+            return Ok(());
+        },
+    };
+
+    // Short arrays of scalars are kept on a single line, unless `max_width`
+    // decides instead.
+    if format.max_width.is_none() && value.len() <= format.inline_arrays && value.iter().all(is_scalar) {
+        punctuation(writer, format, "[")?;
+        writer.write_char(' ')?;
+        for (i, elem) in value.iter().enumerate() {
+            if i > 0 {
+                punctuation(writer, format, ",")?;
+                writer.write_char(' ')?;
+            }
+            scalar(writer, elem, format)?;
+        }
+        writer.write_char(' ')?;
+        punctuation(writer, format, "]")?;
+
+        return Ok(());
+    }
+
+    punctuation(writer, format, "[")?;
+    writer.write_str(&format.newline)?;
+
+    let column = indent_width(format, depth + 1);
+
+    for (i, elem) in value.iter().enumerate() {
+        let last = i + 1 == value.len();
+        let room = room_after(format, column, last);
+
+        write_indent(writer, indent, depth + 1)?;
+        self::value(writer, elem, format, depth + 1, room)?;
+
+        if !last {
+            punctuation(writer, format, ",")?;
+        }
+        writer.write_str(&format.newline)?;
+    }
+
+    write_indent(writer, indent, depth)?;
+    punctuation(writer, format, "]")?;
+
+    Ok(())
+}
+
+fn object<W: Write>(writer: &mut W, value: &HashMap<String, Json>, format: &Format, depth: usize) -> Result<(), Error>
+{
+    let indent = match format.indent {
+        Some(ref indent) if !value.is_empty() => indent,
+
+        // Compact output.
+        _ => {
+            punctuation(writer, format, "{")?;
+            for (i, (k, v)) in entries(value, format).into_iter().enumerate() {
+                if i > 0 {
+                    punctuation(writer, format, ",")?;
+                }
+                write_key(writer, k, format)?;
+                write_colon(writer, format)?;
+                serialize(writer, v, format, depth)?;
+            }
+            punctuation(writer, format, "}")?;
+
+            return Ok(());
+        },
+    };
+
+    // Scalar values start on the same column, containers right after the key.
+    let width = if format.align_values {
+        value.iter()
+            .filter(|(_, v)| is_scalar(v))
+            .map(|(k, _)| key_width(k, format))
+            .max()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    punctuation(writer, format, "{")?;
+    writer.write_str(&format.newline)?;
+
+    for (i, (k, v)) in entries(value, format).into_iter().enumerate() {
+        let last = i + 1 == value.len();
+
+        write_indent(writer, indent, depth + 1)?;
+        write_key(writer, k, format)?;
+        write_colon(writer, format)?;
+
+        let mut column = indent_width(format, depth + 1) + key_width(k, format) + colon_width(format);
+
+        if is_scalar(v) {
+            for _ in key_width(k, format)..width {
+                writer.write_char(' ')?;
+                column += 1;
+            }
+        }
+
+        self::value(writer, v, format, depth + 1, room_after(format, column, last))?;
+
+        if !last {
+            punctuation(writer, format, ",")?;
+        }
+        writer.write_str(&format.newline)?;
+    }
+
+    write_indent(writer, indent, depth)?;
+    punctuation(writer, format, "}")?;
+
+    Ok(())
+}
+
+pub fn entries<'a>(value: &'a HashMap<String, Json>, format: &Format) -> Vec<(&'a String, &'a Json)>
+{
+    let mut entries: Vec<(&String, &Json)> = value.iter().collect();
+
+    if !matches!(format.sort_keys, KeyOrder::Unsorted) {
+        entries.sort_by(|a, b| format.sort_keys.compare(a.0, b.0));
+    }
+
+    entries
+}
+
+// Number of characters the escaped and quoted key takes.
+pub fn key_width(key: &str, format: &Format) -> usize
+{
+    let mut measure = Measure::new(usize::MAX);
+    let _ = escape_into(&mut measure, key, format);
+
+    measure.width
+}
+
+pub fn colon_width(format: &Format) -> usize
+{
+    if format.space_after_colon { 2 } else { 1 }
+}
+
+// Column on which a line starts at `depth`, counting each character of the
+// indentation as one.
+fn indent_width(format: &Format, depth: usize) -> usize
+{
+    match format.indent {
+        Some(ref indent) => indent.chars().count() * depth,
+        None             => 0,
+    }
+}
+
+// Room left for a value starting on `column`, keeping one character for the
+// comma following all but the last element.
+fn room_after(format: &Format, column: usize, last: bool) -> usize
+{
+    let comma = if last { 0 } else { 1 };
+
+    format.max_width.unwrap_or(0).saturating_sub(column + comma)
+}
+
+fn is_scalar(json: &Json) -> bool
+{
+    !matches!(json, Json::Array(_) | Json::Object(_))
+}
+
+pub fn write_colon<W: Write>(writer: &mut W, format: &Format) -> fmt::Result
+{
+    punctuation(writer, format, ":")?;
+
+    if format.space_after_colon {
+        writer.write_char(' ')?;
+    }
+
+    Ok(())
+}
+
+pub fn write_indent<W: Write>(writer: &mut W, indent: &str, depth: usize) -> fmt::Result
+{
+    for _ in 0..depth {
+        writer.write_str(indent)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::mem;
+use std::str::Chars;
+
+use crate::error::Error;
+use crate::json::Json;
+use crate::number::Number;
+use crate::parser::{ boolean, null, number, string_into };
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Null,
+    Boolean(bool),
+    Number(Number),
+    // Byte range inside the tape's string buffer.
+    String(usize, usize),
+    // Number of elements and index of the node following the last descendant.
+    Array(usize, usize),
+    // Children alternate between a String key and its value.
+    Object(usize, usize),
+}
+
+/// A read-only document stored in two flat buffers: one for the nodes, laid
+/// out in document order, and one for the bytes of every string.
+///
+/// Building and dropping a `Tape` costs a handful of allocations no matter
+/// how big the document is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tape {
+    nodes:   Vec<Node>,
+    strings: String,
+}
+
+// Maps every object key seen so far to its range in the string buffer.
+type Symbols = Option<HashMap<String, (usize, usize)>>;
+
+impl Tape {
+    pub fn parse(text: &str) -> Result<Tape, Error>
+    {
+        Tape::parse_with(text, None)
+    }
+
+    /// Parses `text` storing the bytes of each distinct object key only once,
+    /// which pays off for arrays of objects sharing the same shape.
+    ///
+    /// Only the tape is interned: `Json` objects own their keys, so
+    /// `to_json` allocates each of them again.
+    pub fn parse_interned(text: &str) -> Result<Tape, Error>
+    {
+        Tape::parse_with(text, Some(HashMap::new()))
+    }
+
+    fn parse_with(text: &str, mut symbols: Symbols) -> Result<Tape, Error>
+    {
+        let mut slice    = text.chars();
+        let mut peekable = (&mut slice).peekable();
+
+        let mut tape = Tape {
+            nodes:   vec![],
+            strings: String::new(),
+        };
+
+        tape.parse_node(&mut peekable, &mut symbols)?;
+
+        skip_whitespace(&mut peekable);
+
+        if let Some(chr) = peekable.peek() {
+            return Err(Error::InvalidCharacter(chr.to_string()));
+        }
+
+        tape.nodes.shrink_to_fit();
+        tape.strings.shrink_to_fit();
+
+        Ok(tape)
+    }
+
+    /// Bytes allocated on the heap by this tape.
+    pub fn heap_size(&self) -> usize
+    {
+        self.nodes.capacity() * mem::size_of::<Node>() + self.strings.capacity()
+    }
+
+    /// Bytes of string and key text stored by this tape, whatever the
+    /// capacity of its buffer.
+    pub fn strings_len(&self) -> usize
+    {
+        self.strings.len()
+    }
+
+    pub fn root(&self) -> TapeRef<'_>
+    {
+        TapeRef { tape: self, index: 0 }
+    }
+
+    pub fn to_json(&self) -> Json
+    {
+        self.root().to_json()
+    }
+
+    fn parse_node(&mut self, slice: &mut Peekable<&mut Chars>, symbols: &mut Symbols) -> Result<(), Error>
+    {
+        skip_whitespace(slice);
+
+        let current = match slice.peek() {
+            Some(chr) => *chr,
+            None      => { return Err(Error::UnexpectedEof); },
+        };
+
+        match current {
+            'n' => {
+                null(slice)?;
+                self.nodes.push(Node::Null);
+            },
+            'f' | 't' => {
+                if let Json::Boolean(value) = boolean(slice)? {
+                    self.nodes.push(Node::Boolean(value));
+                }
+            },
+            '0'..='9' | '-' => {
+                if let Json::Number(value) = number(slice)? {
+                    self.nodes.push(Node::Number(value));
+                }
+            },
+            '"' => {
+                self.parse_string(slice)?;
+            },
+            '[' => {
+                self.parse_container(slice, symbols, false)?;
+            },
+            '{' => {
+                self.parse_container(slice, symbols, true)?;
+            },
+
+            _ => { return Err(Error::InvalidCharacter(current.to_string())); }
+        }
+
+        Ok(())
+    }
+
+    fn parse_string(&mut self, slice: &mut Peekable<&mut Chars>) -> Result<(), Error>
+    {
+        let start = self.strings.len();
+        string_into(slice, &mut self.strings)?;
+
+        self.nodes.push(Node::String(start, self.strings.len()));
+        Ok(())
+    }
+
+    fn parse_key(&mut self, slice: &mut Peekable<&mut Chars>, symbols: &mut Symbols) -> Result<(), Error>
+    {
+        let symbols = match symbols {
+            Some(symbols) => symbols,
+            None          => { return self.parse_string(slice); },
+        };
+
+        let start = self.strings.len();
+        string_into(slice, &mut self.strings)?;
+
+        let range = match symbols.get(&self.strings[start..]) {
+            Some(range) => {
+                // Already known, drop the copy we just decoded.
+                self.strings.truncate(start);
+                *range
+            },
+            None => {
+                let range = (start, self.strings.len());
+                symbols.insert(String::from(&self.strings[start..]), range);
+                range
+            },
+        };
+
+        self.nodes.push(Node::String(range.0, range.1));
+        Ok(())
+    }
+
+    fn parse_container(&mut self, slice: &mut Peekable<&mut Chars>, symbols: &mut Symbols, is_object: bool) -> Result<(), Error>
+    {
+        let close = if is_object { '}' } else { ']' };
+        let start = self.nodes.len();
+        let mut len = 0;
+
+        // Placeholder, patched once the closing bracket is found.
+        self.nodes.push(Node::Null);
+        slice.next();
+
+        skip_whitespace(slice);
+
+        if slice.peek() == Some(&close) {
+            slice.next();
+
+        } else {
+            loop {
+                if is_object {
+                    skip_whitespace(slice);
+
+                    match slice.peek() {
+                        Some('"') => { self.parse_key(slice, symbols)?; },
+                        Some(chr) => { return Err(Error::InvalidCharacter(chr.to_string())); },
+                        None      => { return Err(Error::UnexpectedEof); },
+                    }
+
+                    skip_whitespace(slice);
+
+                    match slice.next() {
+                        Some(':') => {},
+                        Some(chr) => { return Err(Error::InvalidCharacter(chr.to_string())); },
+                        None      => { return Err(Error::UnexpectedEof); },
+                    }
+                }
+
+                self.parse_node(slice, symbols)?;
+                len += 1;
+
+                skip_whitespace(slice);
+
+                match slice.next() {
+                    Some(',') => {},
+                    Some(chr) if chr == close => { break },
+                    Some(chr) => { return Err(Error::InvalidCharacter(chr.to_string())); },
+                    None      => { return Err(Error::UnexpectedEof); },
+                }
+            }
+        }
+
+        let end = self.nodes.len();
+        self.nodes[start] = if is_object { Node::Object(len, end) } else { Node::Array(len, end) };
+
+        Ok(())
+    }
+
+    fn push_json(&mut self, json: &Json)
+    {
+        match json {
+            Json::Null           => { self.nodes.push(Node::Null); },
+            Json::Boolean(value) => { self.nodes.push(Node::Boolean(*value)); },
+            Json::Number(value)  => { self.nodes.push(Node::Number(value.clone())); },
+            Json::String(value)  => { self.push_str(value); },
+            Json::Array(value)   => {
+                let start = self.nodes.len();
+                self.nodes.push(Node::Null);
+
+                for elem in value {
+                    self.push_json(elem);
+                }
+
+                self.nodes[start] = Node::Array(value.len(), self.nodes.len());
+            },
+            Json::Object(value)  => {
+                let start = self.nodes.len();
+                self.nodes.push(Node::Null);
+
+                for (k, v) in value {
+                    self.push_str(k);
+                    self.push_json(v);
+                }
+
+                self.nodes[start] = Node::Object(value.len(), self.nodes.len());
+            },
+        }
+    }
+
+    fn push_str(&mut self, value: &str)
+    {
+        let start = self.strings.len();
+        self.strings.push_str(value);
+
+        self.nodes.push(Node::String(start, self.strings.len()));
+    }
+
+    // Index of the node right after `index` and all of its descendants.
+    fn skip(&self, index: usize) -> usize
+    {
+        match self.nodes[index] {
+            Node::Array(_, end) | Node::Object(_, end) => end,
+            _ => index + 1,
+        }
+    }
+}
+
+impl<'a> From<&'a Json> for Tape
+{
+    fn from(json: &'a Json) -> Tape
+    {
+        let mut tape = Tape {
+            nodes:   vec![],
+            strings: String::new(),
+        };
+
+        tape.push_json(json);
+        tape
+    }
+}
+
+impl From<Json> for Tape
+{
+    fn from(json: Json) -> Tape
+    {
+        Tape::from(&json)
+    }
+}
+
+impl<'a> From<&'a Tape> for Json
+{
+    fn from(tape: &'a Tape) -> Json
+    {
+        tape.to_json()
+    }
+}
+
+impl From<Tape> for Json
+{
+    fn from(tape: Tape) -> Json
+    {
+        tape.to_json()
+    }
+}
+
+/// A borrowed view of a single node inside a `Tape`.
+#[derive(Clone, Copy, Debug)]
+pub struct TapeRef<'a> {
+    tape:  &'a Tape,
+    index: usize,
+}
+
+impl<'a> TapeRef<'a> {
+    pub fn is_null(&self) -> bool
+    {
+        self.node() == &Node::Null
+    }
+
+    pub fn is_array(&self) -> bool
+    {
+        matches!(self.node(), Node::Array(..))
+    }
+
+    pub fn is_object(&self) -> bool
+    {
+        matches!(self.node(), Node::Object(..))
+    }
+
+    pub fn as_bool(&self) -> Option<bool>
+    {
+        match self.node() {
+            Node::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&'a Number>
+    {
+        match self.node() {
+            Node::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&'a str>
+    {
+        match self.node() {
+            Node::String(start, end) => Some(&self.tape.strings[*start..*end]),
+            _ => None,
+        }
+    }
+
+    /// Number of elements of an array or entries of an object, zero otherwise.
+    pub fn len(&self) -> usize
+    {
+        match self.node() {
+            Node::Array(len, _) | Node::Object(len, _) => *len,
+            _ => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.len() == 0
+    }
+
+    /// Looks up an array element by position.
+    pub fn at(&self, position: usize) -> Option<TapeRef<'a>>
+    {
+        if !self.is_array() {
+            return None;
+        }
+
+        self.elements().nth(position)
+    }
+
+    /// Looks up an object entry by key.
+    pub fn get(&self, key: &str) -> Option<TapeRef<'a>>
+    {
+        self.entries()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Iterates over the elements of an array.
+    pub fn elements(&self) -> TapeElements<'a>
+    {
+        let (index, end) = match self.node() {
+            Node::Array(_, end) => (self.index + 1, *end),
+            _ => (self.index, self.index),
+        };
+
+        TapeElements { tape: self.tape, index, end }
+    }
+
+    /// Iterates over the entries of an object, in document order.
+    pub fn entries(&self) -> TapeEntries<'a>
+    {
+        let (index, end) = match self.node() {
+            Node::Object(_, end) => (self.index + 1, *end),
+            _ => (self.index, self.index),
+        };
+
+        TapeEntries { tape: self.tape, index, end }
+    }
+
+    pub fn to_json(&self) -> Json
+    {
+        match self.node() {
+            Node::Null           => Json::Null,
+            Node::Boolean(value) => Json::Boolean(*value),
+            Node::Number(value)  => Json::Number(value.clone()),
+            Node::String(..)     => Json::String(String::from(self.as_str().unwrap())),
+            Node::Array(..)      => Json::Array(self.elements().map(|v| v.to_json()).collect()),
+            Node::Object(..)     => Json::Object(
+                self.entries().map(|(k, v)| (String::from(k), v.to_json())).collect()
+            ),
+        }
+    }
+
+    fn node(&self) -> &'a Node
+    {
+        &self.tape.nodes[self.index]
+    }
+}
+
+pub struct TapeElements<'a> {
+    tape:  &'a Tape,
+    index: usize,
+    end:   usize,
+}
+
+impl<'a> Iterator for TapeElements<'a>
+{
+    type Item = TapeRef<'a>;
+
+    fn next(&mut self) -> Option<TapeRef<'a>>
+    {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let elem = TapeRef { tape: self.tape, index: self.index };
+        self.index = self.tape.skip(self.index);
+
+        Some(elem)
+    }
+}
+
+pub struct TapeEntries<'a> {
+    tape:  &'a Tape,
+    index: usize,
+    end:   usize,
+}
+
+impl<'a> Iterator for TapeEntries<'a>
+{
+    type Item = (&'a str, TapeRef<'a>);
+
+    fn next(&mut self) -> Option<(&'a str, TapeRef<'a>)>
+    {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let key   = TapeRef { tape: self.tape, index: self.index };
+        let value = TapeRef { tape: self.tape, index: self.index + 1 };
+        self.index = self.tape.skip(self.index + 1);
+
+        Some((key.as_str().unwrap_or(""), value))
+    }
+}
+
+fn skip_whitespace(slice: &mut Peekable<&mut Chars>)
+{
+    while let Some(' ') | Some('\r') | Some('\n') | Some('\t') = slice.peek() {
+        slice.next();
+    }
+}
@@ -0,0 +1,329 @@
+use std::io::{ self, BufRead, BufReader, Read };
+use std::str;
+
+use crate::error::{ Error, Position };
+use crate::format::Format;
+use crate::parser::{ number, string_into };
+use crate::writer::JsonWriter;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Expect {
+    // A value, or the end of the array just opened.
+    FirstElement,
+    Value,
+    // A key, or the end of the object just opened.
+    FirstKey,
+    Key,
+    // A comma or the end of the innermost container, or the end of input.
+    Next,
+}
+
+/// Reads a document from `reader` and writes it back to `writer` following
+/// `format`, one token at a time and without building a `Json` tree.
+/// Numbers are copied exactly as written, while strings are decoded and
+/// escaped again.
+///
+/// Memory use depends on how deeply the document is nested and on its longest
+/// string or number, not on its size. Malformed input fails with
+/// `Error::Syntax`, giving the position where the offending token starts;
+/// whatever was written before it is left in `writer`.
+pub fn transcode<R: Read, W: io::Write>(reader: R, writer: W, format: Format) -> Result<W, Error>
+{
+    let mut source = Source::new(reader);
+    let mut output = JsonWriter::new(writer, format);
+
+    // Whether each open container is an object.
+    let mut stack  = vec![];
+    let mut expect = Expect::Value;
+    let mut token  = String::new();
+
+    loop {
+        source.skip_whitespace()?;
+
+        let position = source.position;
+        let current  = match source.peek()? {
+            Some(chr) => chr,
+            None if expect == Expect::Next && stack.is_empty() => { break; },
+            None => { return Err(source.error(Error::UnexpectedEof)); },
+        };
+
+        match (expect, current) {
+            (Expect::FirstElement, ']') | (Expect::FirstKey, '}') => {
+                source.next()?;
+                stack.pop();
+
+                if current == ']' { output.end_array()?; } else { output.end_object()?; }
+                expect = Expect::Next;
+            },
+            (Expect::FirstElement, _) | (Expect::Value, _) => {
+                match current {
+                    '{' => {
+                        source.next()?;
+                        output.begin_object()?;
+                        stack.push(true);
+                        expect = Expect::FirstKey;
+                    },
+                    '[' => {
+                        source.next()?;
+                        output.begin_array()?;
+                        stack.push(false);
+                        expect = Expect::FirstElement;
+                    },
+                    '"' => {
+                        source.string(&mut token)?;
+                        output.string(&token)?;
+                        expect = Expect::Next;
+                    },
+                    '0'..='9' | '-' => {
+                        // Numbers are copied as written, so none loses
+                        // precision or range on the way.
+                        source.number(&mut token)?;
+                        output.number_text(&token)?;
+                        expect = Expect::Next;
+                    },
+                    't' | 'f' | 'n' => {
+                        source.literal(&mut token)?;
+
+                        match token.as_str() {
+                            "true"  => { output.boolean(true)?; },
+                            "false" => { output.boolean(false)?; },
+                            "null"  => { output.null()?; },
+                            _ => {
+                                return Err(Error::Syntax(position, Box::new(Error::InvalidCharacter(token))));
+                            },
+                        }
+                        expect = Expect::Next;
+                    },
+                    _ => {
+                        return Err(source.error(Error::InvalidCharacter(current.to_string())));
+                    },
+                }
+            },
+            (Expect::FirstKey, '"') | (Expect::Key, '"') => {
+                source.string(&mut token)?;
+                output.key(&token)?;
+                source.skip_whitespace()?;
+
+                match source.peek()? {
+                    Some(':') => { source.next()?; },
+                    Some(chr) => { return Err(source.error(Error::InvalidCharacter(chr.to_string()))); },
+                    None      => { return Err(source.error(Error::UnexpectedEof)); },
+                }
+                expect = Expect::Value;
+            },
+            (Expect::Next, ',') if !stack.is_empty() => {
+                source.next()?;
+                expect = if stack[stack.len() - 1] { Expect::Key } else { Expect::Value };
+            },
+            (Expect::Next, ']') if stack.last() == Some(&false) => {
+                source.next()?;
+                stack.pop();
+                output.end_array()?;
+            },
+            (Expect::Next, '}') if stack.last() == Some(&true) => {
+                source.next()?;
+                stack.pop();
+                output.end_object()?;
+            },
+            _ => {
+                return Err(source.error(Error::InvalidCharacter(current.to_string())));
+            },
+        }
+    }
+
+    output.finish()
+}
+
+// Decodes UTF-8 from a reader one character at a time, keeping track of
+// where the next one starts.
+struct Source<R: Read> {
+    reader:   BufReader<R>,
+    peeked:   Option<char>,
+    position: Position,
+}
+
+impl<R: Read> Source<R> {
+    fn new(reader: R) -> Source<R>
+    {
+        Source {
+            reader:   BufReader::new(reader),
+            peeked:   None,
+            position: Position { line: 1, column: 1, offset: 0 },
+        }
+    }
+
+    fn error(&self, error: Error) -> Error
+    {
+        Error::Syntax(self.position, Box::new(error))
+    }
+
+    fn peek(&mut self) -> Result<Option<char>, Error>
+    {
+        if self.peeked.is_none() {
+            self.peeked = self.decode()?;
+        }
+
+        Ok(self.peeked)
+    }
+
+    fn next(&mut self) -> Result<Option<char>, Error>
+    {
+        let current = self.peek()?;
+        self.peeked = None;
+
+        if let Some(chr) = current {
+            self.position.offset += chr.len_utf8();
+
+            if chr == '\n' {
+                self.position.line  += 1;
+                self.position.column = 1;
+            } else {
+                self.position.column += 1;
+            }
+        }
+
+        Ok(current)
+    }
+
+    fn decode(&mut self) -> Result<Option<char>, Error>
+    {
+        let first = match self.byte()? {
+            Some(byte) => byte,
+            None       => { return Ok(None); },
+        };
+
+        let width = match first {
+            0x00..=0x7F => { return Ok(Some(char::from(first))); },
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => { return Err(self.error(invalid_utf8(first))); },
+        };
+
+        let mut bytes = [first, 0, 0, 0];
+
+        for byte in bytes.iter_mut().take(width).skip(1) {
+            *byte = match self.byte()? {
+                Some(byte) => byte,
+                None       => { return Err(self.error(Error::UnexpectedEof)); },
+            };
+        }
+
+        match str::from_utf8(&bytes[..width]) {
+            Ok(text) => Ok(text.chars().next()),
+            Err(_)   => Err(self.error(invalid_utf8(first))),
+        }
+    }
+
+    fn byte(&mut self) -> Result<Option<u8>, Error>
+    {
+        let byte = match self.reader.fill_buf()? {
+            [] => { return Ok(None); },
+            buffer => buffer[0],
+        };
+
+        self.reader.consume(1);
+        Ok(Some(byte))
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), Error>
+    {
+        while let Some(' ') | Some('\r') | Some('\n') | Some('\t') = self.peek()? {
+            self.next()?;
+        }
+
+        Ok(())
+    }
+
+    // Reads a quoted string and decodes it into `token`.
+    fn string(&mut self, token: &mut String) -> Result<(), Error>
+    {
+        let position = self.position;
+        let mut raw  = String::new();
+
+        raw.push('"');
+        self.next()?;
+
+        loop {
+            let here = self.position;
+
+            match self.next()? {
+                Some('"')  => { raw.push('"'); break; },
+                Some('\\') => {
+                    raw.push('\\');
+
+                    match self.next()? {
+                        Some(chr) => { raw.push(chr); },
+                        None      => { return Err(self.error(Error::UnexpectedEof)); },
+                    }
+                },
+                Some(chr) if chr < ' ' => {
+                    return Err(Error::Syntax(here, Box::new(Error::InvalidCharacter(format!("U+{:04X}", chr as u32)))));
+                },
+                Some(chr)  => { raw.push(chr); },
+                None       => { return Err(self.error(Error::UnexpectedEof)); },
+            }
+        }
+
+        token.clear();
+
+        let mut slice    = raw.chars();
+        let mut peekable = (&mut slice).peekable();
+
+        string_into(&mut peekable, token).map_err(|error| Error::Syntax(position, Box::new(error)))
+    }
+
+    // Reads a number into `token`, checking it is valid.
+    fn number(&mut self, token: &mut String) -> Result<(), Error>
+    {
+        let position = self.position;
+
+        self.word(token, |chr| matches!(chr, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))?;
+
+        let mut slice    = token.chars();
+        let mut peekable = (&mut slice).peekable();
+
+        let result = match number(&mut peekable) {
+            // Digits were missing at the end of the token, not of the input.
+            Err(Error::UnexpectedEof) => match self.peek()? {
+                Some(chr) => { return Err(self.error(Error::InvalidCharacter(chr.to_string()))); },
+                None      => { return Err(self.error(Error::UnexpectedEof)); },
+            },
+            result => result,
+        };
+
+        let result = match peekable.next() {
+            Some(chr) => Err(Error::InvalidCharacter(chr.to_string())),
+            None      => result,
+        };
+
+        result.map(|_| ()).map_err(|error| Error::Syntax(position, Box::new(error)))
+    }
+
+    fn literal(&mut self, token: &mut String) -> Result<(), Error>
+    {
+        self.word(token, |chr| chr.is_ascii_alphabetic())
+    }
+
+    // Reads into `token` every character accepted by `accept`.
+    fn word<F: Fn(char) -> bool>(&mut self, token: &mut String, accept: F) -> Result<(), Error>
+    {
+        token.clear();
+
+        while let Some(chr) = self.peek()? {
+            if !accept(chr) {
+                break;
+            }
+
+            token.push(chr);
+            self.next()?;
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_utf8(byte: u8) -> Error
+{
+    Error::InvalidCharacter(format!("\\x{:02X}", byte))
+}
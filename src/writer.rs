@@ -0,0 +1,283 @@
+use std::fmt::{ self, Write };
+use std::io;
+
+use crate::color::{ paint, punctuation, Role };
+use crate::error::Error;
+use crate::escape::escape_into;
+use crate::format::Format;
+use crate::json::Json;
+use crate::number::Number;
+use crate::serializer::{ colon_width, key_width, serialize, serialize_at, write_colon, write_indent, write_key };
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Container {
+    Array,
+    Object,
+}
+
+/// Writes a document piece by piece, without building a `Json` tree first.
+///
+/// Calls are checked as they come: keys are only accepted inside objects,
+/// every key must be followed by exactly one value and `finish` fails while
+/// containers are still open. Options needing to see a whole container before
+/// writing it, like `align_values`, `inline_arrays` and `sort_keys`, are
+/// ignored; `max_width` only applies within values passed to `value`.
+///
+/// A call failing on an invalid value, like a NaN with `NonFinite::Error`,
+/// leaves the writer as it was. Once the underlying writer fails though, the
+/// output may be cut anywhere and every later call fails too.
+pub struct JsonWriter<W: io::Write> {
+    writer: W,
+    format: Format,
+    // Open containers and how many values each one holds so far.
+    stack:  Vec<(Container, usize)>,
+    // Inside an object, a key was written and its value is still missing.
+    keyed:  bool,
+    // Column following the last key, where its value starts.
+    column: usize,
+    root:   bool,
+    // The underlying writer failed, possibly halfway through a call.
+    failed: bool,
+}
+
+impl<W: io::Write> JsonWriter<W> {
+    pub fn new(writer: W, format: Format) -> JsonWriter<W>
+    {
+        JsonWriter {
+            writer,
+            format,
+            stack:  vec![],
+            keyed:  false,
+            column: 0,
+            root:   false,
+            failed: false,
+        }
+    }
+
+    pub fn begin_object(&mut self) -> Result<(), Error>
+    {
+        self.write_value(|w, format| Ok(punctuation(w, format, "{")?))?;
+
+        self.stack.push((Container::Object, 0));
+        Ok(())
+    }
+
+    pub fn end_object(&mut self) -> Result<(), Error>
+    {
+        self.end(Container::Object, "}")
+    }
+
+    pub fn begin_array(&mut self) -> Result<(), Error>
+    {
+        self.write_value(|w, format| Ok(punctuation(w, format, "[")?))?;
+
+        self.stack.push((Container::Array, 0));
+        Ok(())
+    }
+
+    pub fn end_array(&mut self) -> Result<(), Error>
+    {
+        self.end(Container::Array, "]")
+    }
+
+    pub fn key(&mut self, key: &str) -> Result<(), Error>
+    {
+        self.check()?;
+
+        match self.stack.last() {
+            Some((Container::Object, _)) if !self.keyed => {},
+            Some((Container::Object, _)) => {
+                return Err(Error::InvalidCall(String::from("expecting a value, got a key")));
+            },
+            _ => {
+                return Err(Error::InvalidCall(String::from("key outside of an object")));
+            },
+        }
+
+        let mut buffer = String::new();
+
+        self.separator(&mut buffer)?;
+        write_key(&mut buffer, key, &self.format)?;
+        write_colon(&mut buffer, &self.format)?;
+
+        self.write(&buffer)?;
+
+        let indent = self.format.indent.as_ref().map_or(0, |indent| indent.chars().count());
+
+        self.column = indent * self.stack.len() + key_width(key, &self.format) + colon_width(&self.format);
+        self.keyed  = true;
+        self.count();
+        Ok(())
+    }
+
+    /// Writes a whole value, which may be a container.
+    pub fn value(&mut self, value: &Json) -> Result<(), Error>
+    {
+        let keyed  = self.keyed;
+        let depth  = self.stack.len();
+        let column = self.column;
+
+        self.write_value(|w, format| {
+            if keyed {
+                serialize_at(w, value, format, depth, column)
+            } else {
+                serialize(w, value, format, depth)
+            }
+        })
+    }
+
+    pub fn string(&mut self, value: &str) -> Result<(), Error>
+    {
+        self.write_value(|w, format| paint(w, format, Role::String, |w| Ok(escape_into(w, value, format)?)))
+    }
+
+    pub fn number(&mut self, value: Number) -> Result<(), Error>
+    {
+        self.value(&Json::Number(value))
+    }
+
+    // Writes a number as it was read, which must be valid JSON.
+    pub(crate) fn number_text(&mut self, text: &str) -> Result<(), Error>
+    {
+        self.write_value(|w, format| paint(w, format, Role::Number, |w| Ok(w.write_str(text)?)))
+    }
+
+    pub fn boolean(&mut self, value: bool) -> Result<(), Error>
+    {
+        self.value(&Json::Boolean(value))
+    }
+
+    pub fn null(&mut self) -> Result<(), Error>
+    {
+        self.value(&Json::Null)
+    }
+
+    /// Checks that the document is complete, then flushes and returns the
+    /// underlying writer.
+    pub fn finish(mut self) -> Result<W, Error>
+    {
+        self.check()?;
+
+        if !self.stack.is_empty() {
+            return Err(Error::InvalidCall(String::from("unclosed container")));
+        }
+
+        if !self.root {
+            return Err(Error::InvalidCall(String::from("empty document")));
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    pub fn get_ref(&self) -> &W
+    {
+        &self.writer
+    }
+
+    // Once the underlying writer failed, the output can't be trusted anymore.
+    fn check(&self) -> Result<(), Error>
+    {
+        if self.failed {
+            return Err(Error::InvalidCall(String::from("writer failed on an earlier call")));
+        }
+
+        Ok(())
+    }
+
+    // Writes a value where one is expected. Nothing changes unless `callback`
+    // succeeds, so an invalid value can be replaced by another one.
+    fn write_value<F>(&mut self, callback: F) -> Result<(), Error>
+        where F: FnOnce(&mut String, &Format) -> Result<(), Error>
+    {
+        self.check()?;
+
+        let mut buffer = String::new();
+
+        match self.stack.last() {
+            None if self.root => {
+                return Err(Error::InvalidCall(String::from("more than one root value")));
+            },
+            Some((Container::Array, _)) => { self.separator(&mut buffer)?; },
+            Some((Container::Object, _)) if !self.keyed => {
+                return Err(Error::InvalidCall(String::from("expecting a key, got a value")));
+            },
+            _ => {},
+        }
+
+        callback(&mut buffer, &self.format)?;
+
+        self.write(&buffer)?;
+
+        match self.stack.last() {
+            None => { self.root = true; },
+            Some((Container::Array, _)) => { self.count(); },
+            Some((Container::Object, _)) => { self.keyed = false; },
+        }
+
+        Ok(())
+    }
+
+    // Comma and line break needed before the next key or array element.
+    fn separator(&self, buffer: &mut String) -> fmt::Result
+    {
+        if let Some((_, count)) = self.stack.last() {
+            if *count > 0 {
+                punctuation(buffer, &self.format, ",")?;
+            }
+        }
+
+        if let Some(ref indent) = self.format.indent {
+            buffer.push_str(&self.format.newline);
+            write_indent(buffer, indent, self.stack.len())?;
+        }
+
+        Ok(())
+    }
+
+    fn count(&mut self)
+    {
+        if let Some((_, count)) = self.stack.last_mut() {
+            *count += 1;
+        }
+    }
+
+    fn end(&mut self, container: Container, close: &str) -> Result<(), Error>
+    {
+        self.check()?;
+
+        let count = match self.stack.last() {
+            Some((open, count)) if *open == container && !self.keyed => *count,
+            Some((Container::Object, _)) if self.keyed => {
+                return Err(Error::InvalidCall(String::from("key without a value")));
+            },
+            _ => {
+                return Err(Error::InvalidCall(format!("unexpected {}", close)));
+            },
+        };
+
+        let depth      = self.stack.len() - 1;
+        let mut buffer = String::new();
+
+        // Empty containers stay on one line.
+        if let (Some(ref indent), true) = (&self.format.indent, count > 0) {
+            buffer.push_str(&self.format.newline);
+            write_indent(&mut buffer, indent, depth)?;
+        }
+
+        punctuation(&mut buffer, &self.format, close)?;
+
+        self.write(&buffer)?;
+
+        self.stack.pop();
+        Ok(())
+    }
+
+    fn write(&mut self, text: &str) -> Result<(), Error>
+    {
+        let result = self.writer.write_all(text.as_bytes());
+
+        self.failed = result.is_err();
+        Ok(result?)
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+extern crate simple_json;
+use simple_json::{ Json, Number };
+
+#[test]
+fn kinds()
+{
+    let json = Json::parse("[{}, [], \"s\", 1, true, null]").unwrap();
+    let kinds: Vec<[bool; 6]> = json.as_array().unwrap().iter()
+        .map(|v| [v.is_object(), v.is_array(), v.is_string(), v.is_number(), v.is_boolean(), v.is_null()])
+        .collect();
+
+    for (i, kind) in kinds.iter().enumerate() {
+        for (j, is) in kind.iter().enumerate() {
+            assert_eq!(*is, i == j);
+        }
+    }
+}
+
+#[test]
+fn borrow()
+{
+    let json = Json::parse("{\"s\": \"text\", \"b\": false, \"a\": [1], \"n\": 2.5}").unwrap();
+
+    assert_eq!(json.as_object().map(HashMap::len), Some(4));
+    assert_eq!(json["s"].as_str(), Some("text"));
+    assert_eq!(json["b"].as_bool(), Some(false));
+    assert_eq!(json["a"].as_array(), Some(&vec![Json::from(1_u64)]));
+    assert_eq!(json["n"].as_number(), Some(&Number::Float(2.5)));
+
+    assert_eq!(json.as_array(), None);
+    assert_eq!(json["s"].as_bool(), None);
+    assert_eq!(json["b"].as_str(), None);
+    assert_eq!(json["a"].as_object(), None);
+    assert_eq!(json["s"].as_number(), None);
+    assert_eq!(json["s"].as_f64(), None);
+}
+
+#[test]
+fn borrow_mut()
+{
+    let mut json = Json::parse("{\"a\": [1]}").unwrap();
+
+    json.as_object_mut().unwrap().insert(String::from("b"), Json::Null);
+    json["a"].as_array_mut().unwrap().push(Json::from(2_u64));
+
+    assert_eq!(json, Json::parse("{\"a\": [1, 2], \"b\": null}").unwrap());
+    assert_eq!(json["a"].as_object_mut(), None);
+    assert_eq!(json.as_array_mut(), None);
+}
+
+#[test]
+fn numbers()
+{
+    assert_eq!(Json::from(7_u64).as_u64(), Some(7));
+    assert_eq!(Json::from(7_u64).as_i64(), Some(7));
+    assert_eq!(Json::from(7_u64).as_f64(), Some(7.0));
+
+    assert_eq!(Json::from(-7_i64).as_u64(), None);
+    assert_eq!(Json::from(-7_i64).as_i64(), Some(-7));
+    assert_eq!(Json::from(u64::MAX).as_i64(), None);
+
+    // Floats convert when they hold an integer that fits.
+    assert_eq!(Json::from(3.0).as_u64(), Some(3));
+    assert_eq!(Json::from(-3.0).as_i64(), Some(-3));
+    assert_eq!(Json::from(3.5).as_i64(), None);
+    assert_eq!(Json::from(-3.0).as_u64(), None);
+    assert_eq!(Json::from(18446744073709551616.0).as_u64(), None);
+    assert_eq!(Json::from(-9223372036854775808.0).as_i64(), Some(i64::MIN));
+    assert_eq!(Json::from(9223372036854775808.0).as_i64(), None);
+    assert_eq!(Json::from(f64::NAN).as_i64(), None);
+    assert_eq!(Json::from(f64::INFINITY).as_u64(), None);
+
+    assert_eq!(Number::Unsigned(u64::MAX).as_f64(), 18446744073709551616.0);
+}
+
+#[test]
+fn take()
+{
+    let mut json = Json::parse("{\"big\": [1, 2, 3]}").unwrap();
+
+    let big = json["big"].take();
+
+    assert_eq!(big, Json::parse("[1, 2, 3]").unwrap());
+    assert_eq!(json["big"], Json::Null);
+}
+
+#[test]
+fn into()
+{
+    let json = Json::parse("{\"a\": \"b\"}").unwrap();
+
+    let mut map = json.into_object().unwrap();
+    assert_eq!(map.remove("a").unwrap().into_string(), Some(String::from("b")));
+
+    assert_eq!(Json::from(vec![Json::Null]).into_array(), Some(vec![Json::Null]));
+    assert_eq!(Json::from(1.5).into_number(), Some(Number::Float(1.5)));
+
+    assert_eq!(Json::Null.into_object(), None);
+    assert_eq!(Json::Null.into_array(), None);
+    assert_eq!(Json::Null.into_string(), None);
+    assert_eq!(Json::Null.into_number(), None);
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+extern crate simple_json;
+use simple_json::{ Error, Json, Number };
+
+macro_rules! test_number {
+    ($bits:expr, $expect:expr) => {{
+        let bits: u64 = $bits;
+        let json      = Json::Number(Number::Float(f64::from_bits(bits)));
+
+        assert_eq!(json.to_canonical_string(), Ok(String::from($expect)), "formatting {:#x}", bits);
+    }};
+}
+
+// RFC 8785, section 3.2.2.
+#[test]
+fn rfc_sample()
+{
+    let text = "{
+        \"numbers\": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+        \"string\": \"\\u20ac$\\u000F\\u000aA'\\u0042\\u0022\\u005c\\\\\\\"\\/\",
+        \"literals\": [null, true, false]
+    }";
+
+    let json = Json::parse(text).unwrap();
+
+    assert_eq!(
+        json.to_canonical_string().unwrap(),
+        "{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"€$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}"
+    );
+}
+
+// RFC 8785, section 3.2.3.
+#[test]
+fn rfc_sorting()
+{
+    let text = "{
+        \"\\u20ac\": \"Euro Sign\",
+        \"\\r\": \"Carriage Return\",
+        \"\\ufb33\": \"Hebrew Letter Dalet With Dagesh\",
+        \"1\": \"One\",
+        \"\\ud83d\\ude00\": \"Emoji: Grinning Face\",
+        \"\\u0080\": \"Control\",
+        \"\\u00f6\": \"Latin Small Letter O With Diaeresis\"
+    }";
+
+    let json = Json::parse(text).unwrap();
+
+    assert_eq!(
+        json.to_canonical_string().unwrap(),
+        "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\"ö\":\"Latin Small Letter O With Diaeresis\",\"€\":\"Euro Sign\",\"😀\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+    );
+}
+
+// RFC 8785, appendix B.
+#[test]
+fn rfc_numbers()
+{
+    test_number!(0x0000000000000000, "0");
+    test_number!(0x8000000000000000, "0");
+    test_number!(0x0000000000000001, "5e-324");
+    test_number!(0x8000000000000001, "-5e-324");
+    test_number!(0x7fefffffffffffff, "1.7976931348623157e+308");
+    test_number!(0xffefffffffffffff, "-1.7976931348623157e+308");
+    test_number!(0x4340000000000000, "9007199254740992");
+    test_number!(0xc340000000000000, "-9007199254740992");
+    test_number!(0x4430000000000000, "295147905179352830000");
+    test_number!(0x44b52d02c7e14af5, "9.999999999999997e+22");
+    test_number!(0x44b52d02c7e14af6, "1e+23");
+    test_number!(0x44b52d02c7e14af7, "1.0000000000000001e+23");
+    test_number!(0x444b1ae4d6e2ef4e, "999999999999999700000");
+    test_number!(0x444b1ae4d6e2ef4f, "999999999999999900000");
+    test_number!(0x444b1ae4d6e2ef50, "1e+21");
+    test_number!(0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7");
+    test_number!(0x3eb0c6f7a0b5ed8d, "0.000001");
+    test_number!(0x41b3de4355555553, "333333333.3333332");
+    test_number!(0x41b3de4355555554, "333333333.33333325");
+    test_number!(0x41b3de4355555555, "333333333.3333333");
+    test_number!(0x41b3de4355555556, "333333333.3333334");
+    test_number!(0x41b3de4355555557, "333333333.33333343");
+    test_number!(0xbecbf647612f3696, "-0.0000033333333333333333");
+    test_number!(0x43143ff3c1cb0959, "1424953923781206.2");
+
+    let nan = Json::Number(Number::Float(f64::from_bits(0x7fffffffffffffff)));
+    assert_eq!(nan.to_canonical_string(), Err(Error::NonFiniteNumber(String::from("NaN"))));
+
+    let infinity = Json::Number(Number::Float(f64::from_bits(0x7ff0000000000000)));
+    assert_eq!(infinity.to_canonical_string(), Err(Error::NonFiniteNumber(String::from("Infinity"))));
+}
+
+#[test]
+fn integers_and_nesting()
+{
+    let mut inner = HashMap::new();
+    inner.insert(String::from("b"), Json::from(vec![Json::from(1_u64), Json::from(-2), Json::from(3.0)]));
+    inner.insert(String::from("a"), Json::Object(HashMap::new()));
+
+    let mut outer = HashMap::new();
+    outer.insert(String::from("z"), Json::from(inner));
+    outer.insert(String::from("y"), Json::from(u64::MAX));
+
+    assert_eq!(
+        Json::from(outer).to_canonical_string().unwrap(),
+        "{\"y\":18446744073709552000,\"z\":{\"a\":{},\"b\":[1,-2,3]}}"
+    );
+}
+
+#[test]
+fn write_canonical()
+{
+    let json = Json::parse("{\"b\": 1, \"a\": [true]}").unwrap();
+
+    let mut buffer = vec![];
+    json.write_canonical_to(&mut buffer).unwrap();
+
+    assert_eq!(buffer, b"{\"a\":[true],\"b\":1}");
+}
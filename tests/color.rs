@@ -0,0 +1,147 @@
+use std::fs::File;
+
+extern crate simple_json;
+use simple_json::{ Format, Json, JsonWriter, KeyOrder, Palette };
+
+fn palette() -> Palette
+{
+    Palette {
+        key:         String::from("1"),
+        string:      String::from("2"),
+        number:      String::from("3"),
+        boolean:     String::from("4"),
+        null:        String::from("5"),
+        punctuation: String::from("6"),
+    }
+}
+
+fn colored(format: Format) -> Format
+{
+    Format { colors: Some(palette()), sort_keys: KeyOrder::Lexicographic, ..format }
+}
+
+fn paint(style: &str, text: &str) -> String
+{
+    format!("\x1b[{}m{}\x1b[0m", style, text)
+}
+
+fn strip(text: &str) -> String
+{
+    let mut stripped = String::new();
+    let mut escape   = false;
+
+    for chr in text.chars() {
+        if escape {
+            escape = chr != 'm';
+        } else if chr == '\x1b' {
+            escape = true;
+        } else {
+            stripped.push(chr);
+        }
+    }
+
+    stripped
+}
+
+#[test]
+fn every_token()
+{
+    let json = Json::parse("{\"a\": [1, \"x\", true, null]}").unwrap();
+
+    let expect = [
+        paint("6", "{"),
+        paint("1", "\"a\""),
+        paint("6", ":"),
+        paint("6", "["),
+        paint("3", "1"),
+        paint("6", ","),
+        paint("2", "\"x\""),
+        paint("6", ","),
+        paint("4", "true"),
+        paint("6", ","),
+        paint("5", "null"),
+        paint("6", "]"),
+        paint("6", "}"),
+    ].concat();
+
+    assert_eq!(json.to_string_with(&colored(Format::new())).unwrap(), expect);
+}
+
+#[test]
+fn empty_style_is_plain()
+{
+    let json = Json::parse("[1, 2]").unwrap();
+
+    let format = Format {
+        colors: Some(Palette { punctuation: String::new(), ..palette() }),
+        ..Format::new()
+    };
+
+    assert_eq!(
+        json.to_string_with(&format).unwrap(),
+        format!("[{},{}]", paint("3", "1"), paint("3", "2"))
+    );
+}
+
+#[test]
+fn same_layout_as_plain()
+{
+    let text = "{ \"integer\": 12, \"float\": 80.5, \"string\": \"A JSON sample\", \"array\": [ 1, 2, 3 ], \"object\": { \"a\": \"b\" }, \"nothing\": null }";
+    let json = Json::parse(text).unwrap();
+
+    let plain = Format { sort_keys: KeyOrder::Lexicographic, ..Format::pretty() };
+
+    let output = json.to_string_with(&colored(Format::pretty())).unwrap();
+    assert_ne!(output, json.to_string_with(&plain).unwrap());
+    assert_eq!(strip(&output), json.to_string_with(&plain).unwrap());
+
+    // Escape sequences don't count towards the width.
+    for max_width in [20, 40, 60, 80, 120] {
+        let plain  = Format { max_width: Some(max_width), ..plain.clone() };
+        let output = json.to_string_with(&colored(plain.clone())).unwrap();
+
+        assert_eq!(strip(&output), json.to_string_with(&plain).unwrap());
+    }
+}
+
+#[test]
+fn non_finite()
+{
+    let json = Json::from(vec![Json::from(f64::NAN)]);
+
+    let format = Format { non_finite: simple_json::NonFinite::String, ..colored(Format::new()) };
+
+    assert_eq!(
+        json.to_string_with(&format).unwrap(),
+        format!("{}{}{}", paint("6", "["), paint("2", "\"NaN\""), paint("6", "]"))
+    );
+}
+
+#[test]
+fn writer()
+{
+    let mut writer = JsonWriter::new(vec![], colored(Format::new()));
+
+    writer.begin_object().unwrap();
+    writer.key("k").unwrap();
+    writer.string("v").unwrap();
+    writer.end_object().unwrap();
+
+    let text = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+    assert_eq!(text, [
+        paint("6", "{"),
+        paint("1", "\"k\""),
+        paint("6", ":"),
+        paint("2", "\"v\""),
+        paint("6", "}"),
+    ].concat());
+}
+
+#[test]
+fn auto_disabled_when_not_a_terminal()
+{
+    let file = File::open("Cargo.toml").unwrap();
+
+    assert_eq!(Palette::auto_for(&file), None);
+}
@@ -1,12 +1,15 @@
-use std::collections::HashMap;
+#![allow(clippy::unnecessary_cast, clippy::useless_conversion)]
+
+use std::collections::{ BTreeMap, HashMap, HashSet };
+use std::convert::{ TryFrom, TryInto };
 
 extern crate simple_json;
-use simple_json::{ Json, Number };
+use simple_json::{ Error, Json, Number };
 
 macro_rules! test_json_to {
     ($T: ty, $json:expr, $value:expr) => {{
         let json = Json::parse($json).unwrap();
-        let val: $T = json.into();
+        let val: $T = json.try_into().unwrap();
         assert_eq!(val, $value);
     }};
 }
@@ -35,18 +38,60 @@ fn json_to_bool()
 fn json_to_number()
 {
     test_json_to!(u64, "1",   1);
-    test_json_to!(u64, "-2",  ((-2 as i64) as u64));
-    test_json_to!(u64, "3.4", 3);
+    test_json_to!(u64, "3.0", 3);
 
     test_json_to!(i64, "5",   5);
     test_json_to!(i64, "-6", -6);
-    test_json_to!(i64, "7.8", 7);
+    test_json_to!(i64, "7.0", 7);
 
     test_json_to!(f64, "9",   9.);
     test_json_to!(f64, "-1", -1.);
     test_json_to!(f64, "0.2", 0.2);
 }
 
+#[test]
+fn json_to_number_out_of_range()
+{
+    let json = Json::parse("[-2, 3.4, 18446744073709551615, 1e300]").unwrap();
+
+    assert_eq!(u64::try_from(&json[0]), Err(Error::OutOfRange(String::from("-2"), "u64")));
+    assert_eq!(u64::try_from(&json[1]), Err(Error::OutOfRange(String::from("3.4"), "u64")));
+    assert_eq!(i64::try_from(&json[1]), Err(Error::OutOfRange(String::from("3.4"), "i64")));
+    assert_eq!(i64::try_from(&json[2]), Err(Error::OutOfRange(String::from("18446744073709551615"), "i64")));
+    assert_eq!(u64::try_from(&json[3]), Err(Error::OutOfRange(String::from("1e+300"), "u64")));
+
+    assert_eq!(u64::try_from(Number::Integer(-1)), Err(Error::OutOfRange(String::from("-1"), "u64")));
+    assert_eq!(i64::try_from(Number::Unsigned(7)), Ok(7));
+    assert_eq!(f64::from(&Number::Unsigned(7)), 7.0);
+}
+
+#[test]
+fn json_to_wrong_type()
+{
+    let json = Json::parse("[{}, [], \"s\", 1, true, null]").unwrap();
+
+    assert_eq!(bool::try_from(&json[0]), Err(Error::InvalidType("boolean", "object")));
+    assert_eq!(String::try_from(&json[1]), Err(Error::InvalidType("string", "array")));
+    assert_eq!(u64::try_from(&json[2]), Err(Error::InvalidType("number", "string")));
+    assert_eq!(i64::try_from(json[4].clone()), Err(Error::InvalidType("number", "boolean")));
+    assert_eq!(f64::try_from(&json[5]), Err(Error::InvalidType("number", "null")));
+    assert_eq!(<()>::try_from(&json[3]), Err(Error::InvalidType("null", "number")));
+    assert_eq!(Vec::<Json>::try_from(json[0].clone()), Err(Error::InvalidType("array", "object")));
+    assert_eq!(HashMap::<String, Json>::try_from(json.clone()), Err(Error::InvalidType("object", "array")));
+}
+
+#[test]
+fn json_by_reference()
+{
+    let json = Json::parse("{\"name\": \"x\", \"n\": 3}").unwrap();
+
+    let name: &str = (&json["name"]).try_into().unwrap();
+    assert_eq!(name, "x");
+    assert_eq!(String::try_from(&json["name"]), Ok(String::from("x")));
+    assert_eq!(u64::try_from(&json["n"]), Ok(3));
+    assert_eq!(HashMap::<String, Json>::try_from(&json).map(|map| map.len()), Ok(2));
+}
+
 #[test]
 fn json_to_string()
 {
@@ -60,8 +105,8 @@ fn json_to_array()
         Json::from(1 as u64),
         Json::from(2.),
         Json::from("String"),
-        Json::from(vec![]),
-        Json::from(HashMap::new()),
+        Json::Array(vec![]),
+        Json::Object(HashMap::new()),
     ])
 }
 
@@ -74,8 +119,8 @@ fn json_to_object()
     map.insert(String::from("i"), Json::from(-3));
     map.insert(String::from("f"), Json::from(2.));
     map.insert(String::from("s"), Json::from("String"));
-    map.insert(String::from("a"), Json::from(vec![]));
-    map.insert(String::from("o"), Json::from(HashMap::new()));
+    map.insert(String::from("a"), Json::Array(vec![]));
+    map.insert(String::from("o"), Json::Object(HashMap::new()));
 
     test_json_to!(HashMap<String, Json>, "{\"u\":1,\"i\":-3,\"f\":2.0,\"s\":\"String\",\"a\":[],\"o\":{}}", map);
 }
@@ -115,16 +160,16 @@ fn array_to_json()
         Json::from(1),
         Json::from(2.),
         Json::from("String"),
-        Json::from(vec![]),
-        Json::from(HashMap::new()),
+        Json::Array(vec![]),
+        Json::Object(HashMap::new()),
     ]);
 
     test_json_from!(vec, Json::Array(vec![
         Json::from(1),
         Json::from(2.),
         Json::from("String"),
-        Json::from(vec![]),
-        Json::from(HashMap::new()),
+        Json::Array(vec![]),
+        Json::Object(HashMap::new()),
     ]));
 }
 
@@ -137,8 +182,8 @@ fn object_to_json()
     map.insert(String::from("i"), Json::from(-3));
     map.insert(String::from("f"), Json::from(2.));
     map.insert(String::from("s"), Json::from("String"));
-    map.insert(String::from("a"), Json::from(vec![]));
-    map.insert(String::from("o"), Json::from(HashMap::new()));
+    map.insert(String::from("a"), Json::Array(vec![]));
+    map.insert(String::from("o"), Json::Object(HashMap::new()));
 
     let mut exp = HashMap::new();
 
@@ -146,8 +191,119 @@ fn object_to_json()
     exp.insert(String::from("i"), Json::from(-3));
     exp.insert(String::from("f"), Json::from(2.));
     exp.insert(String::from("s"), Json::from("String"));
-    exp.insert(String::from("a"), Json::from(vec![]));
-    exp.insert(String::from("o"), Json::from(HashMap::new()));
+    exp.insert(String::from("a"), Json::Array(vec![]));
+    exp.insert(String::from("o"), Json::Object(HashMap::new()));
 
     test_json_from!(map, Json::Object(exp));
 }
+
+#[test]
+fn primitives_to_json()
+{
+    test_json_from!(1_u8,    Json::Number(Number::Unsigned(1)));
+    test_json_from!(2_u16,   Json::Number(Number::Unsigned(2)));
+    test_json_from!(3_u32,   Json::Number(Number::Unsigned(3)));
+    test_json_from!(4_usize, Json::Number(Number::Unsigned(4)));
+    test_json_from!(-5_i8,   Json::Number(Number::Integer(-5)));
+    test_json_from!(-6_i16,  Json::Number(Number::Integer(-6)));
+    test_json_from!(-7_isize, Json::Number(Number::Integer(-7)));
+    test_json_from!(0.5_f32, Json::Number(Number::Float(0.5)));
+    test_json_from!('x',     Json::String(String::from("x")));
+}
+
+#[test]
+fn json_to_primitives()
+{
+    test_json_to!(u8,    "255", 255);
+    test_json_to!(u16,   "2",   2);
+    test_json_to!(u32,   "3",   3);
+    test_json_to!(usize, "4",   4);
+    test_json_to!(i8,    "-128", -128);
+    test_json_to!(i16,   "-6",  -6);
+    test_json_to!(i32,   "7",   7);
+    test_json_to!(isize, "-8",  -8);
+    test_json_to!(f32,   "0.5", 0.5);
+    test_json_to!(char,  "\"\u{e9}\"", '\u{e9}');
+
+    let json = Json::parse("[256, -129, -1, 1e300, \"ab\", \"\"]").unwrap();
+
+    assert_eq!(u8::try_from(&json[0]), Err(Error::OutOfRange(String::from("256"), "u8")));
+    assert_eq!(i8::try_from(&json[1]), Err(Error::OutOfRange(String::from("-129"), "i8")));
+    assert_eq!(usize::try_from(&json[2]), Err(Error::OutOfRange(String::from("-1"), "usize")));
+    assert_eq!(f32::try_from(&json[3]), Err(Error::OutOfRange(String::from("1e+300"), "f32")));
+    assert_eq!(char::try_from(&json[4]), Err(Error::InvalidLength(1, 2)));
+    assert_eq!(char::try_from(&json[5]), Err(Error::InvalidLength(1, 0)));
+}
+
+#[test]
+fn containers_to_json()
+{
+    let mut set = HashSet::new();
+    set.insert("only");
+
+    let mut tree = BTreeMap::new();
+    tree.insert(String::from("a"), vec![1_u8]);
+
+    test_json_from!(vec![1_u8, 2, 3], Json::parse("[1, 2, 3]").unwrap());
+    test_json_from!([true, false], Json::parse("[true, false]").unwrap());
+    test_json_from!((1_u8, "two", 3.5), Json::parse("[1, \"two\", 3.5]").unwrap());
+    test_json_from!(set, Json::parse("[\"only\"]").unwrap());
+    test_json_from!(tree, Json::parse("{\"a\": [1]}").unwrap());
+    test_json_from!(Some(1_u8), Json::parse("1").unwrap());
+    test_json_from!(None::<u8>, Json::Null);
+    test_json_from!(Box::new("boxed"), Json::parse("\"boxed\"").unwrap());
+    test_json_from!(vec![Some(1_u8), None], Json::parse("[1, null]").unwrap());
+}
+
+#[test]
+fn json_to_containers()
+{
+    test_json_to!(Vec<u32>, "[1, 2, 3]", vec![1, 2, 3]);
+    test_json_to!(Vec<Vec<i8>>, "[[1], [], [-1, 2]]", vec![vec![1], vec![], vec![-1, 2]]);
+    test_json_to!([u8; 3], "[1, 2, 3]", [1, 2, 3]);
+    test_json_to!((u8, String, bool), "[1, \"two\", true]", (1, String::from("two"), true));
+    test_json_to!((f64,), "[0.5]", (0.5,));
+    test_json_to!(Option<u32>, "null", None);
+    test_json_to!(Option<u32>, "3", Some(3));
+    test_json_to!(Box<String>, "\"boxed\"", Box::new(String::from("boxed")));
+    test_json_to!(Vec<Option<bool>>, "[true, null]", vec![Some(true), None]);
+    test_json_to!(Option<Vec<u32>>, "[1, 2]", Some(vec![1, 2]));
+    test_json_to!(Option<Vec<u32>>, "null", None);
+    test_json_to!(Option<[u8; 2]>, "[1, 2]", Some([1, 2]));
+    test_json_to!(Option<(u8, bool)>, "[1, true]", Some((1, true)));
+    test_json_to!(Option<Option<u8>>, "1", Some(Some(1)));
+    test_json_to!(Box<Vec<Option<String>>>, "[null]", Box::new(vec![None]));
+
+    let mut boxed = HashMap::new();
+    boxed.insert(String::from("a"), -1_i64);
+    test_json_to!(Box<HashMap<String, i64>>, "{\"a\": -1}", Box::new(boxed));
+
+    let mut tree = BTreeMap::new();
+    tree.insert(String::from("a"), 1_i32);
+    tree.insert(String::from("b"), -2_i32);
+    test_json_to!(BTreeMap<String, i32>, "{\"b\": -2, \"a\": 1}", tree);
+
+    let mut map = HashMap::new();
+    map.insert(String::from("a"), vec![0.5_f64]);
+    test_json_to!(HashMap<String, Vec<f64>>, "{\"a\": [0.5]}", map);
+
+    let set: HashSet<String> = ["x", "y"].iter().map(|s| String::from(*s)).collect();
+    test_json_to!(HashSet<String>, "[\"x\", \"y\", \"x\"]", set);
+}
+
+#[test]
+fn json_to_containers_errors()
+{
+    let json = Json::parse("{\"a\": [1, 2], \"b\": [1, \"x\"], \"c\": \"s\"}").unwrap();
+
+    assert_eq!(<[u8; 3]>::try_from(&json["a"]), Err(Error::InvalidLength(3, 2)));
+    assert_eq!(<(u8, u8, u8)>::try_from(&json["a"]), Err(Error::InvalidLength(3, 2)));
+    assert_eq!(<(u8, u8)>::try_from(&json["b"]), Err(Error::InvalidType("number", "string")));
+    assert_eq!(Vec::<u8>::try_from(&json["b"]), Err(Error::InvalidType("number", "string")));
+    assert_eq!(Vec::<u8>::try_from(&json["c"]), Err(Error::InvalidType("array", "string")));
+    assert_eq!(Option::<String>::try_from(&json["a"]), Err(Error::InvalidType("string", "array")));
+    assert_eq!(Option::<Vec<String>>::try_from(&json["a"]), Err(Error::InvalidType("string", "number")));
+    assert_eq!(BTreeMap::<String, u8>::try_from(&json["a"]), Err(Error::InvalidType("object", "array")));
+    assert_eq!(BTreeMap::<String, u8>::try_from(&Json::parse("{\"a\": [1]}").unwrap()), Err(Error::InvalidType("number", "array")));
+    assert_eq!(Vec::<Json>::try_from(&json["b"]), Ok(vec![Json::from(1_u64), Json::from("x")]));
+}
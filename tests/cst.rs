@@ -0,0 +1,190 @@
+extern crate simple_json;
+use simple_json::{ Document, Error, Json, Position };
+
+const CONFIG: &str = "// Deployment settings.
+{
+    \"name\":    \"service\",   // shown in the dashboard
+    \"servers\": [
+        { \"host\": \"a.example\", \"port\": 8080 },
+        { \"host\": \"b.example\", \"port\": 8080 }
+    ],
+    /* Limits, in seconds. */
+    \"timeout\": 30
+}
+";
+
+#[test]
+fn lossless()
+{
+    let document = Document::parse(CONFIG).unwrap();
+
+    assert_eq!(document.as_str(), CONFIG);
+    assert_eq!(document.to_string(), CONFIG);
+    assert_eq!(document.get(&["servers", "1", "host"]), Some(Json::from("b.example")));
+    assert_eq!(document.get(&["servers", "2"]), None);
+    assert_eq!(document.get(&["name", "x"]), None);
+
+    let stripped = "{\"name\": \"service\", \"servers\": [{\"host\": \"a.example\", \"port\": 8080}, {\"host\": \"b.example\", \"port\": 8080}], \"timeout\": 30}";
+    assert_eq!(document.to_json(), Json::parse(stripped).unwrap());
+}
+
+#[test]
+fn set()
+{
+    let mut document = Document::parse(CONFIG).unwrap();
+
+    document.set(&["servers", "0", "port"], &Json::from(9090)).unwrap();
+    document.set(&["name"], &Json::from("api")).unwrap();
+
+    assert_eq!(document.as_str(), CONFIG
+        .replace("\"port\": 8080 },\n", "\"port\": 9090 },\n")
+        .replace("\"service\"", "\"api\""));
+
+    assert_eq!(
+        document.set(&["servers", "0", "user"], &Json::Null),
+        Err(Error::InvalidPath(String::from("/servers/0/user")))
+    );
+}
+
+#[test]
+fn set_root()
+{
+    let mut document = Document::parse("  /* empty */ [] ").unwrap();
+
+    document.set(&[], &Json::from(vec![Json::from(1), Json::Null])).unwrap();
+
+    assert_eq!(document.as_str(), "  /* empty */ [1,null] ");
+}
+
+#[test]
+fn insert_after_trailing_comment()
+{
+    let mut document = Document::parse("{\n  \"a\": 1, // one\n  \"b\": 2 // two\n}\n").unwrap();
+
+    document.insert(&[], "c", &Json::from(3)).unwrap();
+
+    assert_eq!(document.as_str(), "{\n  \"a\": 1, // one\n  \"b\": 2, // two\n  \"c\": 3\n}\n");
+}
+
+#[test]
+fn insert_copies_layout()
+{
+    let mut document = Document::parse(CONFIG).unwrap();
+
+    document.insert(&[], "retries", &Json::from(5)).unwrap();
+    document.insert(&["servers", "1"], "tls", &Json::from(true)).unwrap();
+
+    assert_eq!(document.as_str(), CONFIG
+        .replace("\"port\": 8080 }\n", "\"port\": 8080, \"tls\": true }\n")
+        .replace("\"timeout\": 30\n", "\"timeout\": 30,\n    \"retries\": 5\n"));
+}
+
+#[test]
+fn insert_existing_key()
+{
+    let mut document = Document::parse("{ \"a\" : 1 }").unwrap();
+
+    document.insert(&[], "a", &Json::from("x")).unwrap();
+    assert_eq!(document.as_str(), "{ \"a\" : \"x\" }");
+
+    document.insert(&[], "b", &Json::Null).unwrap();
+    assert_eq!(document.as_str(), "{ \"a\" : \"x\", \"b\" : null }");
+}
+
+#[test]
+fn insert_into_empty()
+{
+    let mut document = Document::parse("{\"a\": {}, \"b\": [ ], \"c\": {/* none */}}").unwrap();
+
+    document.insert(&["a"], "k", &Json::from(1)).unwrap();
+    document.push(&["b"], &Json::from(2)).unwrap();
+    document.insert(&["c"], "k", &Json::from(3)).unwrap();
+
+    assert_eq!(document.as_str(), "{\"a\": {\"k\": 1}, \"b\": [2], \"c\": {\"k\": 3/* none */}}");
+    assert_eq!(document.insert(&["b"], "k", &Json::Null), Err(Error::InvalidPath(String::from("/b"))));
+    assert_eq!(document.push(&["a"], &Json::Null), Err(Error::InvalidPath(String::from("/a"))));
+}
+
+#[test]
+fn push()
+{
+    let mut document = Document::parse("[\n\t1,\n\t2\n]").unwrap();
+
+    document.push(&[], &Json::from("three")).unwrap();
+
+    assert_eq!(document.as_str(), "[\n\t1,\n\t2,\n\t\"three\"\n]");
+}
+
+#[test]
+fn remove_lines()
+{
+    let mut document = Document::parse(CONFIG).unwrap();
+
+    assert_eq!(document.remove(&["name"]), Ok(Json::from("service")));
+    assert_eq!(document.remove(&["servers", "1"]), Json::parse("{\"host\": \"b.example\", \"port\": 8080}"));
+
+    assert_eq!(document.as_str(), "// Deployment settings.
+{
+    \"servers\": [
+        { \"host\": \"a.example\", \"port\": 8080 }
+    ],
+    /* Limits, in seconds. */
+    \"timeout\": 30
+}
+");
+}
+
+#[test]
+fn remove_inline()
+{
+    let mut document = Document::parse("[1, 2, 3, {\"a\": 4, \"b\": 5}]").unwrap();
+
+    document.remove(&["1"]).unwrap();
+    assert_eq!(document.as_str(), "[1, 3, {\"a\": 4, \"b\": 5}]");
+
+    document.remove(&["2", "b"]).unwrap();
+    assert_eq!(document.as_str(), "[1, 3, {\"a\": 4}]");
+
+    document.remove(&["2", "a"]).unwrap();
+    assert_eq!(document.as_str(), "[1, 3, {}]");
+
+    document.remove(&["2"]).unwrap();
+    assert_eq!(document.as_str(), "[1, 3]");
+
+    assert_eq!(document.remove(&["2"]), Err(Error::InvalidPath(String::from("/2"))));
+    assert_eq!(document.remove(&[]), Err(Error::InvalidPath(String::new())));
+}
+
+#[test]
+fn remove_last_line()
+{
+    let mut document = Document::parse("{\r\n  \"a\": 1,\r\n  \"b\": 2\r\n}").unwrap();
+
+    document.remove(&["b"]).unwrap();
+
+    assert_eq!(document.as_str(), "{\r\n  \"a\": 1\r\n}");
+}
+
+#[test]
+fn syntax_errors()
+{
+    let error = |line, column, offset, error| Err(Error::Syntax(Position { line, column, offset }, Box::new(error)));
+
+    assert_eq!(Document::parse("{\n  \"a\": 1,\n}"), error(3, 1, 12, Error::InvalidCharacter(String::from("}"))));
+    assert_eq!(Document::parse("[1] /* open"), error(1, 12, 11, Error::UnexpectedEof));
+    assert_eq!(Document::parse("[tru]"), error(1, 2, 1, Error::InvalidCharacter(String::from("tru"))));
+    assert_eq!(Document::parse("[\"\\x\"]"), error(1, 2, 1, Error::InvalidCharacter(String::from("x"))));
+    assert_eq!(Document::parse("1 2"), error(1, 3, 2, Error::InvalidCharacter(String::from("2"))));
+}
+
+#[test]
+fn escaped_paths()
+{
+    let document = Document::parse("{\"a/b\": {\"~\": 1}}").unwrap();
+
+    assert_eq!(document.get(&["a/b", "~"]), Some(Json::from(1_u64)));
+    assert_eq!(
+        document.clone().set(&["a/b", "~x"], &Json::Null),
+        Err(Error::InvalidPath(String::from("/a~1b/~0x")))
+    );
+}
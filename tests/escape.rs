@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+extern crate simple_json;
+use simple_json::{ Error, Format, Json };
+
+macro_rules! test {
+    ($format:expr, $value:expr, $expect:expr) => {{
+        let json = Json::from($value);
+        let text = json.to_string_with(&$format).unwrap();
+
+        assert_eq!(text, $expect);
+        assert_eq!(Json::parse(&text), Ok(json));
+    }};
+}
+
+#[test]
+fn always_escaped()
+{
+    let format = Format::default();
+
+    test!(format, "quote \" backslash \\ slash /", "\"quote \\\" backslash \\\\ slash /\"");
+    test!(format, "\u{08}\u{0C}\n\r\t", "\"\\b\\f\\n\\r\\t\"");
+    test!(format, "\u{00}\u{01}\u{1F}\u{7F}", "\"\\u0000\\u0001\\u001f\u{7F}\"");
+    test!(format, "<a & b> \u{2028} ü 😀", "\"<a & b> \u{2028} ü 😀\"");
+}
+
+#[test]
+fn ascii_only()
+{
+    let format = Format { ascii_only: true, ..Format::default() };
+
+    test!(format, "ü", "\"\\u00fc\"");
+    test!(format, "あ", "\"\\u3042\"");
+    test!(format, "😀!", "\"\\ud83d\\ude00!\"");
+    test!(format, "plain", "\"plain\"");
+}
+
+#[test]
+fn html_safe()
+{
+    let format = Format { html_safe: true, ..Format::default() };
+
+    test!(format, "</script><b>&amp;", "\"\\u003c/script\\u003e\\u003cb\\u003e\\u0026amp;\"");
+}
+
+#[test]
+fn line_separators()
+{
+    let format = Format { escape_separators: true, ..Format::default() };
+
+    test!(format, "a\u{2028}b\u{2029}c", "\"a\\u2028b\\u2029c\"");
+}
+
+#[test]
+fn object_keys()
+{
+    let format = Format { ascii_only: true, html_safe: true, ..Format::default() };
+
+    let mut map = HashMap::new();
+    map.insert(String::from("<\"ké\\y\">"), Json::from("\n"));
+
+    test!(format, map, "{\"\\u003c\\\"k\\u00e9\\\\y\\\"\\u003e\":\"\\n\"}");
+}
+
+#[test]
+fn parse_surrogates()
+{
+    assert_eq!(Json::parse("\"\\uD83D\\uDE00\""), Ok(Json::from("😀")));
+    assert_eq!(Json::parse("\"\\u00411\""), Ok(Json::from("A1")));
+
+    assert_eq!(Json::parse("\"\\ud83d\""), Err(Error::InvalidCharacter(String::from("U+D83D"))));
+    assert_eq!(Json::parse("\"\\ud83dx\""), Err(Error::InvalidCharacter(String::from("U+D83D"))));
+    assert_eq!(Json::parse("\"\\ude00\""), Err(Error::InvalidCharacter(String::from("U+de00"))));
+    assert_eq!(Json::parse("\"\\u12\""), Err(Error::InvalidCharacter(String::from("U+12"))));
+    assert_eq!(Json::parse("\"unterminated"), Err(Error::UnexpectedEof));
+}
+
+#[test]
+fn parse_control_characters()
+{
+    assert_eq!(Json::parse("\"a\nb\""), Err(Error::InvalidCharacter(String::from("U+000A"))));
+    assert_eq!(Json::parse("\"\u{0}\""), Err(Error::InvalidCharacter(String::from("U+0000"))));
+    assert_eq!(Json::parse("{\"\t\": 1}"), Err(Error::InvalidCharacter(String::from("U+0009"))));
+    assert_eq!(Json::parse("\"\u{7f}\""), Ok(Json::from("\u{7f}")));
+}
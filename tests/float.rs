@@ -0,0 +1,141 @@
+extern crate simple_json;
+use simple_json::{ Json, Number };
+
+macro_rules! test_parse {
+    ($text:expr) => {{
+        let expected = $text.parse::<f64>().unwrap();
+
+        match Json::parse($text) {
+            Ok(Json::Number(Number::Float(value))) => {
+                assert_eq!(value.to_bits(), expected.to_bits(), "parsing {}", $text);
+            },
+            other => panic!("Expecting a float for {}, got {:?}", $text, other),
+        }
+    }};
+}
+
+macro_rules! test_format {
+    ($value:expr, $text:expr) => {{
+        assert_eq!(Number::Float($value).to_string(), $text);
+    }};
+}
+
+// Deterministic pseudo-random sequence, so failures can be reproduced.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64
+    {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.0 >> 11
+    }
+}
+
+#[test]
+fn parse_edge_cases()
+{
+    test_parse!("0.0");
+    test_parse!("-0.0");
+    test_parse!("1e0");
+    test_parse!("0.1");
+    test_parse!("0.3");
+    test_parse!("3.14159265358979323846264338327950288");
+    test_parse!("9007199254740993.0");
+    test_parse!("9007199254740995.0");
+    test_parse!("1.7976931348623157e308");
+    test_parse!("1.7976931348623159e308");
+    test_parse!("2.2250738585072011e-308");
+    test_parse!("2.2250738585072014e-308");
+    test_parse!("4.9406564584124654e-324");
+    test_parse!("2.4703282292062327e-324");
+    test_parse!("2.4703282292062328e-324");
+    test_parse!("1e-400");
+    test_parse!("1e400");
+    test_parse!("-1E+22");
+    test_parse!("1e23");
+    test_parse!("8.988465674311579e307");
+    test_parse!("7.2057594037927933e16");
+    test_parse!("123456789012345678901234567890e-10");
+    test_parse!("0.000000000000000000000000000000000000001e39");
+    test_parse!("1e05");
+}
+
+#[test]
+fn parse_matches_std()
+{
+    let mut rng = Lcg(42);
+
+    for _ in 0..20000 {
+        let digits   = 1 + rng.next() % 19;
+        let mantissa = rng.next() % 10_u64.pow(digits as u32);
+        let exponent = (rng.next() % 660) as i64 - 340;
+        let point    = rng.next() % (digits + 1);
+
+        let mut text = mantissa.to_string();
+        if point > 0 && (point as usize) < text.len() {
+            text.insert(text.len() - point as usize, '.');
+        }
+        text.push_str(&format!("e{}", exponent));
+
+        test_parse!(text.as_str());
+    }
+}
+
+#[test]
+fn parse_invalid_numbers()
+{
+    assert!(Json::parse("-").is_err());
+    assert!(Json::parse("1.").is_err());
+    assert!(Json::parse("-.5").is_err());
+    assert!(Json::parse("1e").is_err());
+    assert!(Json::parse("1e+").is_err());
+    assert!(Json::parse("2.e3").is_err());
+}
+
+#[test]
+fn parse_integer_overflow()
+{
+    assert_eq!(Json::parse("18446744073709551615"), Ok(Json::Number(Number::Unsigned(u64::MAX))));
+    assert_eq!(Json::parse("18446744073709551616"), Ok(Json::Number(Number::Float(18446744073709551616.))));
+    assert_eq!(Json::parse("-9223372036854775808"), Ok(Json::Number(Number::Integer(i64::MIN))));
+    assert_eq!(Json::parse("-9223372036854775809"), Ok(Json::Number(Number::Float(-9223372036854775809.))));
+}
+
+#[test]
+fn format_shortest()
+{
+    test_format!(3.,        "3.0");
+    test_format!(-3.,       "-3.0");
+    test_format!(0.1,       "0.1");
+    test_format!(0.3,       "0.3");
+    test_format!(0.1 + 0.2, "0.30000000000000004");
+    test_format!(80.5,      "80.5");
+    test_format!(1e20,      "100000000000000000000.0");
+    test_format!(1e21,      "1e+21");
+    test_format!(1.5e300,   "1.5e+300");
+    test_format!(0.000001,  "0.000001");
+    test_format!(1.5e-7,    "1.5e-7");
+    test_format!(5e-324,    "5e-324");
+    test_format!(f64::MAX,  "1.7976931348623157e+308");
+}
+
+#[test]
+fn round_trip_keeps_variant()
+{
+    let mut rng = Lcg(7);
+
+    for _ in 0..20000 {
+        let value = f64::from_bits(rng.next() << 11 | rng.next() & 0x7FF);
+        if !value.is_finite() {
+            continue;
+        }
+
+        let text = Number::Float(value).to_string();
+        assert_eq!(Json::parse(&text), Ok(Json::Number(Number::Float(value))), "formatting {}", text);
+    }
+
+    for value in &[0., 1., 2., 3., 10., 1e15, 1e21, 12345678.] {
+        let text = Json::from(*value).to_string();
+        assert_eq!(Json::parse(&text), Ok(Json::from(*value)));
+    }
+}
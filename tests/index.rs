@@ -0,0 +1,99 @@
+extern crate simple_json;
+use simple_json::Json;
+
+fn document() -> Json
+{
+    Json::parse("{\"servers\": [{\"host\": \"a\", \"port\": 80}, {\"host\": \"b\"}], \"name\": \"x\"}").unwrap()
+}
+
+#[test]
+fn read()
+{
+    let json = document();
+    let key  = String::from("name");
+
+    assert_eq!(json["servers"][0]["port"], Json::from(80_u64));
+    assert_eq!(json["servers"][1]["host"], Json::from("b"));
+    assert_eq!(json[&key], Json::from("x"));
+    assert_eq!(json[key.as_str()], Json::from("x"));
+}
+
+#[test]
+fn read_missing()
+{
+    let json = document();
+
+    assert_eq!(json["servers"][1]["port"], Json::Null);
+    assert_eq!(json["servers"][5]["host"], Json::Null);
+    assert_eq!(json["name"]["first"], Json::Null);
+    assert_eq!(json[0], Json::Null);
+    assert_eq!(json["missing"]["deeper"][3], Json::Null);
+}
+
+#[test]
+fn get()
+{
+    let mut json = document();
+
+    assert_eq!(json.get("name"), Some(&Json::from("x")));
+    assert_eq!(json.get("missing"), None);
+    assert_eq!(json.get(0), None);
+    assert_eq!(json["servers"].get(1).and_then(|s| s.get("host")), Some(&Json::from("b")));
+    assert_eq!(json["servers"].get(2), None);
+
+    *json.get_mut("name").unwrap() = Json::from("y");
+    assert_eq!(json["name"], Json::from("y"));
+    assert_eq!(json.get_mut("missing"), None);
+    assert_eq!(json.get("missing"), None);
+}
+
+#[test]
+fn write()
+{
+    let mut json = document();
+
+    json["servers"][1]["port"] = Json::from(8080);
+    json["name"] = Json::from("z");
+
+    assert_eq!(json["servers"][1]["port"], Json::from(8080));
+    assert_eq!(json["name"], Json::from("z"));
+}
+
+#[test]
+fn auto_vivify()
+{
+    let mut json = Json::Null;
+
+    json["a"]["b"][0] = Json::from(true);
+    json["a"]["b"][1]["x"] = Json::Null;
+    json["a"]["c"] = Json::from("d");
+
+    assert_eq!(json, Json::parse("{\"a\": {\"b\": [true, {\"x\": null}], \"c\": \"d\"}}").unwrap());
+}
+
+#[test]
+#[should_panic]
+fn write_past_the_end()
+{
+    let mut json = Json::parse("[1, 2]").unwrap();
+
+    json[3] = Json::Null;
+}
+
+#[test]
+#[should_panic]
+fn write_into_scalar()
+{
+    let mut json = document();
+
+    json["name"]["first"] = Json::Null;
+}
+
+#[test]
+#[should_panic]
+fn write_array_index_into_object()
+{
+    let mut json = document();
+
+    json[0] = Json::Null;
+}
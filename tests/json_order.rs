@@ -0,0 +1,113 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{ BTreeMap, HashMap, HashSet };
+use std::hash::{ Hash, Hasher };
+
+extern crate simple_json;
+use simple_json::Json;
+
+fn hash(json: &Json) -> u64
+{
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn parse(text: &str) -> Json
+{
+    Json::parse(text).unwrap()
+}
+
+#[test]
+fn kinds()
+{
+    let mut values = vec![
+        parse("{}"),
+        parse("[]"),
+        parse("\"\""),
+        parse("-1e300"),
+        parse("true"),
+        parse("false"),
+        parse("null"),
+    ];
+
+    values.sort();
+
+    assert_eq!(values, vec![
+        parse("null"),
+        parse("false"),
+        parse("true"),
+        parse("-1e300"),
+        parse("\"\""),
+        parse("[]"),
+        parse("{}"),
+    ]);
+}
+
+#[test]
+fn within_kinds()
+{
+    assert!(parse("2") < parse("10"));
+    assert!(parse("1") < parse("1.5"));
+    assert!(parse("\"10\"") < parse("\"2\""));
+    assert!(parse("[1, 2]") < parse("[1, 2, 0]"));
+    assert!(parse("[1, 3]") > parse("[1, 2, 9]"));
+    assert!(parse("[null]") < parse("[false]"));
+
+    // Objects compare their entries sorted by key.
+    assert!(parse("{\"a\": 1}") < parse("{\"a\": 2}"));
+    assert!(parse("{\"b\": 1, \"a\": 9}") < parse("{\"b\": 0}"));
+    assert!(parse("{\"a\": 1}") < parse("{\"a\": 1, \"b\": 0}"));
+}
+
+#[test]
+fn hash_matches_equality()
+{
+    let text = "{\"k0\": 0, \"k1\": 1, \"k2\": 2, \"k3\": 3, \"k4\": 4, \"k5\": 5, \"k6\": 6, \"k7\": 7}";
+
+    // Maps built separately iterate in different orders.
+    for _ in 0..16 {
+        assert_eq!(hash(&parse(text)), hash(&parse(text)));
+    }
+
+    assert_eq!(parse("[1, 2.0]"), parse("[1.0, 2]"));
+    assert_eq!(hash(&parse("[1, 2.0]")), hash(&parse("[1.0, 2]")));
+    assert_eq!(hash(&parse("-0.0")), hash(&parse("0")));
+    assert_ne!(hash(&parse("[]")), hash(&parse("{}")));
+}
+
+#[test]
+fn collections()
+{
+    let set = vec![
+        parse("{\"id\": 1, \"tags\": [\"a\"]}"),
+        parse("{\"tags\": [\"a\"], \"id\": 1.0}"),
+        parse("{\"id\": 2}"),
+        parse("[1, 2]"),
+        parse("[1, 2]"),
+    ].into_iter().collect::<HashSet<Json>>();
+
+    assert_eq!(set.len(), 3);
+
+    let mut cache = HashMap::new();
+    cache.insert(parse("{\"query\": \"x\", \"page\": 1}"), "first");
+
+    assert_eq!(cache.get(&parse("{\"page\": 1, \"query\": \"x\"}")), Some(&"first"));
+
+    let mut tree = BTreeMap::new();
+    tree.insert(parse("\"b\""), 1);
+    tree.insert(parse("1"), 2);
+    tree.insert(parse("null"), 3);
+
+    assert_eq!(tree.values().cloned().collect::<Vec<_>>(), vec![3, 2, 1]);
+}
+
+#[test]
+fn nan()
+{
+    let nan = Json::from(f64::NAN);
+
+    assert_eq!(nan, nan.clone());
+    assert_eq!(hash(&nan), hash(&Json::from(-f64::NAN)));
+    assert!(nan > Json::from(f64::INFINITY));
+    assert!(nan < Json::from(""));
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+#[macro_use]
+extern crate simple_json;
+use simple_json::{ Json, Number };
+
+#[test]
+fn literals()
+{
+    assert_eq!(json!(null), Json::Null);
+    assert_eq!(json!(true), Json::Boolean(true));
+    assert_eq!(json!(800), Json::Number(Number::Integer(800)));
+    assert_eq!(json!(-2.5), Json::Number(Number::Float(-2.5)));
+    assert_eq!(json!("text"), Json::String(String::from("text")));
+    assert_eq!(json!([]), Json::Array(vec![]));
+    assert_eq!(json!({}), Json::Object(HashMap::new()));
+}
+
+#[test]
+fn nested()
+{
+    let json = json!({
+        "size": { "x": 800, "y": 600 },
+        "tags": ["a", null, [true, {}]],
+    });
+
+    let text = "{\"size\": {\"x\": 800, \"y\": 600}, \"tags\": [\"a\", null, [true, {}]]}";
+
+    assert_eq!(json, Json::parse(text).unwrap());
+}
+
+#[test]
+fn expressions()
+{
+    let w    = 800_u32;
+    let a    = String::from("a");
+    let name = "key";
+    let list = vec![1, 2];
+
+    let json = json!({
+        "size": { "x": w, "y": w / 4 * 3 },
+        "tags": [a, "b"],
+        name: list.len() as u64 + 1,
+        format!("{}-{}", name, 2): -(w as i64),
+        "list": list.clone(),
+        "inner": json!([1, 2]),
+        "maybe": Some(1.5),
+        "none": None::<u8>,
+    });
+
+    let text = "{
+        \"size\": {\"x\": 800, \"y\": 600}, \"tags\": [\"a\", \"b\"], \"key\": 3, \"key-2\": -800,
+        \"list\": [1, 2], \"inner\": [1, 2], \"maybe\": 1.5, \"none\": null
+    }";
+
+    assert_eq!(json, Json::parse(text).unwrap());
+}
+
+#[test]
+fn trailing_commas()
+{
+    assert_eq!(json!([1, 2,]), json!([1, 2]));
+    assert_eq!(json!({"a": 1,}), json!({"a": 1}));
+    assert_eq!(json!([-1, x(), 1 + 1]), Json::parse("[-1, 3, 2]").unwrap());
+}
+
+fn x() -> i32
+{
+    3
+}
+
+#[test]
+fn long_array()
+{
+    let json = json!([
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+        20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39,
+        40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59,
+        60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79,
+        80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99,
+        100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119,
+        120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139,
+        140, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159,
+        160, 161, 162, 163, 164, 165, 166, 167, 168, 169, 170, 171, 172, 173, 174, 175, 176, 177, 178, 179,
+        180, 181, 182, 183, 184, 185, 186, 187, 188, 189, 190, 191, 192, 193, 194, 195, 196, 197, 198, 199,
+        200, 201, 202, 203, 204, 205, 206, 207, 208, 209, 210, 211, 212, 213, 214, 215, 216, 217, 218, 219,
+        220, 221, 222, 223, 224, 225, 226, 227, 228, 229, 230, 231, 232, 233, 234, 235, 236, 237, 238, 239,
+        240, 241, 242, 243, 244, 245, 246, 247, 248, 249, 250, 251, 252, 253, 254, 255, 256, 257, 258, 259,
+        260, 261, 262, 263, 264, 265, 266, 267, 268, 269, 270, 271, 272, 273, 274, 275, 276, 277, 278, 279,
+        280, 281, 282, 283, 284, 285, 286, 287, 288, 289, 290, 291, 292, 293, 294, 295, 296, 297, 298, 299,
+    ]);
+
+    assert_eq!(json, Json::from((0..300).collect::<Vec<i32>>()));
+
+    let json = json!([
+        0, 1, 2, 3, 4, -5, 6, 7, 8, 9, 10, 11, 12, 13, 14,
+        -15, 16, 17, 18, 19, 20, 21, 22, 23, 24, -25, 26, 27, 28, 29,
+        30, 31, 32, 33, 34, -35, 36, 37, 38, 39, 40, 41, 42, 43, 44,
+        -45, 46, 47, 48, 49, 50, 51, 52, 53, 54, -55, 56, 57, 58, 59,
+        60, 61, 62, 63, 64, -65, 66, 67, 68, 69, 70, 71, 72, 73, 74,
+        -75, 76, 77, 78, 79, 80, 81, 82, 83, 84, -85, 86, 87, 88, 89,
+        90, 91, 92, 93, 94, -95, 96, 97, 98, 99, 100, 101, 102, 103, 104,
+        -105, 106, 107, 108, 109, 110, 111, 112, 113, 114, -115, 116, 117, 118, 119,
+        120, 121, 122, 123, 124, -125, 126, 127, 128, 129, 130, 131, 132, 133, 134,
+        -135, 136, 137, 138, 139, 140, 141, 142, 143, 144, -145, 146, 147, 148, 149,
+        150, 151, 152, 153, 154, -155, 156, 157, 158, 159, 160, 161, 162, 163, 164,
+        -165, 166, 167, 168, 169, 170, 171, 172, 173, 174, -175, 176, 177, 178, 179,
+        180, 181, 182, 183, 184, -185, 186, 187, 188, 189, 190, 191, 192, 193, 194,
+        -195, 196, 197, 198, 199, 200, 201, 202, 203, 204, -205, 206, 207, 208, 209,
+        210, 211, 212, 213, 214, -215, 216, 217, 218, 219, 220, 221, 222, 223, 224,
+        -225, 226, 227, 228, 229, 230, 231, 232, 233, 234, -235, 236, 237, 238, 239,
+        240, 241, 242, 243, 244, -245, 246, 247, 248, 249, 250, 251, 252, 253, 254,
+        -255, 256, 257, 258, 259, 260, 261, 262, 263, 264, -265, 266, 267, 268, 269,
+        270, 271, 272, 273, 274, -275, 276, 277, 278, 279, 280, 281, 282, 283, 284,
+        -285, 286, 287, 288, 289, 290, 291, 292, 293, 294, -295, 296, 297, 298, 299,
+    ]);
+
+    assert_eq!(json, Json::from((0..300).map(|n| if n % 10 == 5 { -n } else { n }).collect::<Vec<i32>>()));
+}
+
+#[test]
+fn long_object()
+{
+    let json = json!({
+        "k0": 0, "k1": 1, "k2": 2, "k3": 3, "k4": 4, "k5": 5, "k6": 6, "k7": 7,
+        "k8": 8, "k9": 9, "k10": 10, "k11": 11, "k12": 12, "k13": 13, "k14": 14, "k15": 15,
+        "k16": 16, "k17": 17, "k18": 18, "k19": 19, "k20": 20, "k21": 21, "k22": 22, "k23": 23,
+        "k24": 24, "k25": 25, "k26": 26, "k27": 27, "k28": 28, "k29": 29, "k30": 30, "k31": 31,
+        "k32": 32, "k33": 33, "k34": 34, "k35": 35, "k36": 36, "k37": 37, "k38": 38, "k39": 39,
+        "k40": 40, "k41": 41, "k42": 42, "k43": 43, "k44": 44, "k45": 45, "k46": 46, "k47": 47,
+        "k48": 48, "k49": 49, "k50": 50, "k51": 51, "k52": 52, "k53": 53, "k54": 54, "k55": 55,
+        "k56": 56, "k57": 57, "k58": 58, "k59": 59, "k60": 60, "k61": 61, "k62": 62, "k63": 63,
+        "k64": 64, "k65": 65, "k66": 66, "k67": 67, "k68": 68, "k69": 69, "k70": 70, "k71": 71,
+        "k72": 72, "k73": 73, "k74": 74, "k75": 75, "k76": 76, "k77": 77, "k78": 78, "k79": 79,
+        "k80": 80, "k81": 81, "k82": 82, "k83": 83, "k84": 84, "k85": 85, "k86": 86, "k87": 87,
+        "k88": 88, "k89": 89, "k90": 90, "k91": 91, "k92": 92, "k93": 93, "k94": 94, "k95": 95,
+        "k96": 96, "k97": 97, "k98": 98, "k99": 99, "k100": 100, "k101": 101, "k102": 102, "k103": 103,
+        "k104": 104, "k105": 105, "k106": 106, "k107": 107, "k108": 108, "k109": 109, "k110": 110, "k111": 111,
+        "k112": 112, "k113": 113, "k114": 114, "k115": 115, "k116": 116, "k117": 117, "k118": 118, "k119": 119,
+        "k120": 120, "k121": 121, "k122": 122, "k123": 123, "k124": 124, "k125": 125, "k126": 126, "k127": 127,
+        "k128": 128, "k129": 129, "k130": 130, "k131": 131, "k132": 132, "k133": 133, "k134": 134, "k135": 135,
+        "k136": 136, "k137": 137, "k138": 138, "k139": 139, "k140": 140, "k141": 141, "k142": 142, "k143": 143,
+        "k144": 144, "k145": 145, "k146": 146, "k147": 147, "k148": 148, "k149": 149, "k150": 150, "k151": 151,
+        "k152": 152, "k153": 153, "k154": 154, "k155": 155, "k156": 156, "k157": 157, "k158": 158, "k159": 159,
+        "k160": 160, "k161": 161, "k162": 162, "k163": 163, "k164": 164, "k165": 165, "k166": 166, "k167": 167,
+        "k168": 168, "k169": 169, "k170": 170, "k171": 171, "k172": 172, "k173": 173, "k174": 174, "k175": 175,
+        "k176": 176, "k177": 177, "k178": 178, "k179": 179, "k180": 180, "k181": 181, "k182": 182, "k183": 183,
+        "k184": 184, "k185": 185, "k186": 186, "k187": 187, "k188": 188, "k189": 189, "k190": 190, "k191": 191,
+        "k192": 192, "k193": 193, "k194": 194, "k195": 195, "k196": 196, "k197": 197, "k198": 198, "k199": 199,
+    });
+
+    assert_eq!(json, Json::from((0..200).map(|n| (format!("k{}", n), n)).collect::<HashMap<String, i32>>()));
+
+    let json = json!({
+        "first": -1,
+        "k0": 0, "k1": 1, "k2": 2, "k3": 3, "k4": 4, "k5": 5, "k6": 6, "k7": 7,
+        "k8": 8, "k9": 9, "k10": 10, "k11": 11, "k12": 12, "k13": 13, "k14": 14, "k15": 15,
+        "k16": 16, "k17": 17, "k18": 18, "k19": 19, "k20": 20, "k21": 21, "k22": 22, "k23": 23,
+        "k24": 24, "k25": 25, "k26": 26, "k27": 27, "k28": 28, "k29": 29, "k30": 30, "k31": 31,
+        "k32": 32, "k33": 33, "k34": 34, "k35": 35, "k36": 36, "k37": 37, "k38": 38, "k39": 39,
+        "k40": 40, "k41": 41, "k42": 42, "k43": 43, "k44": 44, "k45": 45, "k46": 46, "k47": 47,
+        "k48": 48, "k49": 49, "k50": 50, "k51": 51, "k52": 52, "k53": 53, "k54": 54, "k55": 55,
+        "k56": 56, "k57": 57, "k58": 58, "k59": 59, "k60": 60, "k61": 61, "k62": 62, "k63": 63,
+        "k64": 64, "k65": 65, "k66": 66, "k67": 67, "k68": 68, "k69": 69, "k70": 70, "k71": 71,
+        "k72": 72, "k73": 73, "k74": 74, "k75": 75, "k76": 76, "k77": 77, "k78": 78, "k79": 79,
+        "k80": 80, "k81": 81, "k82": 82, "k83": 83, "k84": 84, "k85": 85, "k86": 86, "k87": 87,
+        "k88": 88, "k89": 89, "k90": 90, "k91": 91, "k92": 92, "k93": 93, "k94": 94, "k95": 95,
+        "k96": 96, "k97": 97, "k98": 98, "k99": 99, "k100": 100, "k101": 101, "k102": 102, "k103": 103,
+        "k104": 104, "k105": 105, "k106": 106, "k107": 107, "k108": 108, "k109": 109, "k110": 110, "k111": 111,
+        "k112": 112, "k113": 113, "k114": 114, "k115": 115, "k116": 116, "k117": 117, "k118": 118, "k119": 119,
+        "k120": 120, "k121": 121, "k122": 122, "k123": 123, "k124": 124, "k125": 125, "k126": 126, "k127": 127,
+        "k128": 128, "k129": 129, "k130": 130, "k131": 131, "k132": 132, "k133": 133, "k134": 134, "k135": 135,
+        "k136": 136, "k137": 137, "k138": 138, "k139": 139, "k140": 140, "k141": 141, "k142": 142, "k143": 143,
+        "k144": 144, "k145": 145, "k146": 146, "k147": 147, "k148": 148, "k149": 149, "k150": 150, "k151": 151,
+        "k152": 152, "k153": 153, "k154": 154, "k155": 155, "k156": 156, "k157": 157, "k158": 158, "k159": 159,
+        "k160": 160, "k161": 161, "k162": 162, "k163": 163, "k164": 164, "k165": 165, "k166": 166, "k167": 167,
+        "k168": 168, "k169": 169, "k170": 170, "k171": 171, "k172": 172, "k173": 173, "k174": 174, "k175": 175,
+        "k176": 176, "k177": 177, "k178": 178, "k179": 179, "k180": 180, "k181": 181, "k182": 182, "k183": 183,
+        "k184": 184, "k185": 185, "k186": 186, "k187": 187, "k188": 188, "k189": 189, "k190": 190, "k191": 191,
+        "k192": 192, "k193": 193, "k194": 194, "k195": 195, "k196": 196, "k197": 197, "k198": 198, "k199": 199,
+    });
+
+    assert_eq!(json["first"], Json::from(-1));
+    assert_eq!(json["k199"],  Json::from(199));
+}
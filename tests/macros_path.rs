@@ -0,0 +1,13 @@
+extern crate simple_json;
+
+use simple_json::Json;
+
+#[test]
+fn full_path()
+{
+    let json = simple_json::json!({ "a": [1, -2, null], "b": { "c": true } });
+
+    assert_eq!(json["a"][1],   Json::from(-2));
+    assert_eq!(json["a"][2],   Json::Null);
+    assert_eq!(json["b"]["c"], Json::from(true));
+}
@@ -0,0 +1,92 @@
+use std::env;
+use std::fs;
+use std::sync::Arc;
+
+extern crate simple_json;
+use simple_json::{ Error, Json, Ndjson, Number };
+
+fn records(count: usize) -> String
+{
+    let mut text = String::new();
+
+    for i in 0..count {
+        text.push_str(&format!("{{\"id\": {}, \"tags\": [\"a\", \"b\"]}}\n", i));
+    }
+
+    text
+}
+
+#[test]
+fn ordered_matches_sequential()
+{
+    let text   = records(1000);
+    let parsed = Ndjson::new().threads(4).chunk_lines(7).parse(text.clone());
+
+    let mut count = 0;
+
+    for ((number, result), line) in parsed.zip(text.lines()) {
+        count += 1;
+
+        assert_eq!(number, count);
+        assert_eq!(result, Json::parse(line));
+    }
+
+    assert_eq!(count, 1000);
+}
+
+#[test]
+fn unordered_yields_every_record()
+{
+    let text = records(500);
+
+    let mut numbers: Vec<usize> = Ndjson::new()
+        .threads(3)
+        .chunk_lines(11)
+        .ordered(false)
+        .parse(text)
+        .map(|(number, result)| { assert!(result.is_ok()); number })
+        .collect();
+
+    numbers.sort();
+    assert_eq!(numbers, (1..501).collect::<Vec<usize>>());
+}
+
+#[test]
+fn blank_lines_and_errors()
+{
+    let text   = "1\r\n\n  \n[true]\n{\"a\": }\n\"last\"";
+    let parsed = Ndjson::new().threads(2).chunk_lines(2).parse(text).collect::<Vec<_>>();
+
+    assert_eq!(parsed, vec![
+        (1, Ok(Json::Number(Number::Unsigned(1)))),
+        (4, Ok(Json::Array(vec![Json::Boolean(true)]))),
+        (5, Err(Error::UnexpectedEof)),
+        (6, Ok(Json::String(String::from("last")))),
+    ]);
+}
+
+#[test]
+fn parse_file()
+{
+    let path = env::temp_dir().join("simple_json_ndjson_test.ndjson");
+    fs::write(&path, records(50)).unwrap();
+
+    let parsed = Ndjson::new().threads(2).parse_file(&path).unwrap().count();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(parsed, 50);
+}
+
+#[test]
+fn shared_text()
+{
+    let text: Arc<str> = Arc::from(records(100));
+    let parsed = Ndjson::new().threads(3).chunk_lines(1).parse(Arc::clone(&text));
+
+    for ((number, result), line) in parsed.zip(text.lines()) {
+        assert_eq!(result, Json::parse(line), "line {}", number);
+    }
+
+    // Stopping early leaves no worker behind waiting for the iterator.
+    assert_eq!(Ndjson::new().threads(2).chunk_lines(1).parse(text).take(3).count(), 3);
+}
@@ -0,0 +1,74 @@
+use std::convert::TryInto;
+
+extern crate simple_json;
+use simple_json::{ Error, Format, Json, NonFinite, Number };
+
+macro_rules! test {
+    ($policy:expr, $value:expr, $expect:expr) => {{
+        let mut format = Format::new();
+        format.non_finite = $policy;
+
+        assert_eq!(Json::from($value).to_string_with(&format), $expect);
+    }};
+}
+
+#[test]
+fn default_policy_is_null()
+{
+    assert_eq!(Json::from(f64::NAN).to_string(), "null");
+    assert_eq!(Json::from(f64::INFINITY).to_string(), "null");
+    assert_eq!(Json::Array(vec![Json::from(1.5), Json::from(f64::NEG_INFINITY)]).to_string(), "[1.5,null]");
+}
+
+#[test]
+fn error_policy()
+{
+    test!(NonFinite::Error, f64::NAN,          Err(Error::NonFiniteNumber(String::from("NaN"))));
+    test!(NonFinite::Error, f64::INFINITY,     Err(Error::NonFiniteNumber(String::from("Infinity"))));
+    test!(NonFinite::Error, f64::NEG_INFINITY, Err(Error::NonFiniteNumber(String::from("-Infinity"))));
+    test!(NonFinite::Error, 2.5,               Ok(String::from("2.5")));
+
+    let mut format = Format::new();
+    format.non_finite = NonFinite::Error;
+
+    let nested = Json::Array(vec![Json::Array(vec![Json::from(f64::NAN)])]);
+    assert!(nested.to_string_with(&format).is_err());
+}
+
+#[test]
+fn string_and_literal_policies()
+{
+    test!(NonFinite::Null,    f64::NAN,          Ok(String::from("null")));
+    test!(NonFinite::String,  f64::NAN,          Ok(String::from("\"NaN\"")));
+    test!(NonFinite::String,  f64::NEG_INFINITY, Ok(String::from("\"-Infinity\"")));
+    test!(NonFinite::Literal, f64::INFINITY,     Ok(String::from("Infinity")));
+    test!(NonFinite::Literal, f64::NEG_INFINITY, Ok(String::from("-Infinity")));
+}
+
+#[test]
+fn negative_zero()
+{
+    let json = Json::parse("-0").unwrap();
+
+    match json {
+        Json::Number(Number::Float(value)) => {
+            assert_eq!(value, 0.0);
+            assert!(value.is_sign_negative());
+        },
+        _ => panic!("Expecting a float, got {:?}", json),
+    }
+
+    assert_eq!(json.to_string(), "-0.0");
+    assert_eq!(Json::parse("0"), Ok(Json::Number(Number::Unsigned(0))));
+
+    let float: f64 = Number::Float(-0.0).into();
+    assert!(float.is_sign_negative());
+
+    let float: f64 = Json::parse("-0.0").unwrap().try_into().unwrap();
+    assert!(float.is_sign_negative());
+
+    let integer: i64 = Number::Float(-0.0).try_into().unwrap();
+    assert_eq!(integer, 0);
+
+    assert!(Json::parse("[-0]").unwrap() == Json::Array(vec![Json::from(-0.0)]));
+}
@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{ BTreeSet, HashSet };
+use std::hash::{ Hash, Hasher };
+
+extern crate simple_json;
+use simple_json::{ Json, Number };
+
+fn hash(number: &Number) -> u64
+{
+    let mut hasher = DefaultHasher::new();
+    number.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn equal(a: Number, b: Number)
+{
+    assert_eq!(a, b);
+    assert_eq!(b, a);
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert_eq!(hash(&a), hash(&b));
+}
+
+fn less(a: Number, b: Number)
+{
+    assert!(a < b, "{:?} < {:?}", a, b);
+    assert!(b > a, "{:?} > {:?}", b, a);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn across_variants()
+{
+    equal(Number::Unsigned(1), Number::Integer(1));
+    equal(Number::Unsigned(1), Number::Float(1.0));
+    equal(Number::Integer(-1), Number::Float(-1.0));
+    equal(Number::Integer(0), Number::Float(-0.0));
+    equal(Number::Float(0.0), Number::Float(-0.0));
+    equal(Number::Unsigned(1 << 63), Number::Float(9223372036854775808.0));
+    equal(Number::Integer(i64::MIN), Number::Float(-9223372036854775808.0));
+
+    assert_eq!(Json::from(1), Json::from(1_u64));
+    assert_eq!(Json::parse("[1, 2.0]"), Ok(Json::from(vec![1.0, 2.0])));
+}
+
+#[test]
+fn exact()
+{
+    // Both round to the same f64, but aren't the same number.
+    less(Number::Unsigned(9007199254740992), Number::Unsigned(9007199254740993));
+    less(Number::Float(9007199254740992.0), Number::Unsigned(9007199254740993));
+    less(Number::Unsigned(u64::MAX), Number::Float(18446744073709551616.0));
+    less(Number::Integer(i64::MAX), Number::Float(9223372036854775808.0));
+    less(Number::Float(-9223372036854777856.0), Number::Integer(i64::MIN));
+
+    less(Number::Integer(2), Number::Float(2.5));
+    less(Number::Float(-2.5), Number::Integer(-2));
+    less(Number::Integer(-1), Number::Unsigned(0));
+    less(Number::Integer(-3), Number::Float(-2.999999));
+}
+
+#[test]
+fn total_order()
+{
+    let mut numbers = vec![
+        Number::Float(f64::NAN),
+        Number::Float(f64::INFINITY),
+        Number::Unsigned(u64::MAX),
+        Number::Float(0.5),
+        Number::Integer(0),
+        Number::Float(-0.5),
+        Number::Integer(i64::MIN),
+        Number::Float(f64::NEG_INFINITY),
+    ];
+
+    let expect = numbers.iter().rev().cloned().collect::<Vec<_>>();
+
+    numbers.sort();
+    assert_eq!(format!("{:?}", numbers), format!("{:?}", expect));
+
+    equal(Number::Float(f64::NAN), Number::Float(-f64::NAN));
+    less(Number::Float(f64::INFINITY), Number::Float(f64::NAN));
+    less(Number::Unsigned(u64::MAX), Number::Float(f64::NAN));
+}
+
+#[test]
+fn collections()
+{
+    let set: HashSet<Number> = vec![
+        Number::Unsigned(1), Number::Integer(1), Number::Float(1.0),
+        Number::Float(0.0), Number::Float(-0.0), Number::Integer(0),
+        Number::Float(f64::NAN), Number::Float(f64::NAN),
+        Number::Float(1.5),
+    ].into_iter().collect();
+
+    assert_eq!(set.len(), 4);
+
+    let tree: BTreeSet<Number> = set.into_iter().collect();
+    let tree = tree.into_iter().collect::<Vec<_>>();
+
+    assert_eq!(tree[0], Number::Unsigned(0));
+    assert_eq!(tree[1], Number::Unsigned(1));
+    assert_eq!(tree[2], Number::Float(1.5));
+    assert!(matches!(tree[3], Number::Float(value) if value.is_nan()));
+}
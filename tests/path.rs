@@ -0,0 +1,456 @@
+#[macro_use]
+extern crate simple_json;
+use simple_json::{ Error, Json, JsonPath, Position };
+
+fn store() -> Json
+{
+    json!({
+        "store": {
+            "book": [
+                { "category": "reference", "author": "Nigel Rees", "title": "Sayings of the Century", "price": 8.95 },
+                { "category": "fiction", "author": "Evelyn Waugh", "title": "Sword of Honour", "price": 12.99 },
+                { "category": "fiction", "author": "Herman Melville", "title": "Moby Dick", "isbn": "0-553-21311-3", "price": 8.99 },
+                { "category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord of the Rings", "isbn": "0-395-19395-8", "price": 22.99 }
+            ],
+            "bicycle": { "color": "red", "price": 399 }
+        }
+    })
+}
+
+// Values selected, in order.
+fn select(json: &Json, query: &str) -> Vec<Json>
+{
+    json.query(query).unwrap().into_iter().cloned().collect()
+}
+
+// Values selected, for queries going through object members in no
+// particular order.
+fn select_sorted(json: &Json, query: &str) -> Vec<Json>
+{
+    let mut values = select(json, query);
+    values.sort();
+    values
+}
+
+fn sorted(mut values: Vec<Json>) -> Vec<Json>
+{
+    values.sort();
+    values
+}
+
+fn paths(json: &Json, query: &str) -> Vec<String>
+{
+    JsonPath::parse(query).unwrap().query(json).iter().map(|found| found.path()).collect()
+}
+
+#[test]
+fn bookstore()
+{
+    let json = store();
+
+    assert_eq!(
+        select(&json, "$.store.book[*].author"),
+        vec![json!("Nigel Rees"), json!("Evelyn Waugh"), json!("Herman Melville"), json!("J. R. R. Tolkien")]
+    );
+    assert_eq!(
+        select(&json, "$..author"),
+        vec![json!("Nigel Rees"), json!("Evelyn Waugh"), json!("Herman Melville"), json!("J. R. R. Tolkien")]
+    );
+    assert_eq!(
+        select_sorted(&json, "$.store.*"),
+        sorted(vec![json["store"]["book"].clone(), json["store"]["bicycle"].clone()])
+    );
+    assert_eq!(
+        select_sorted(&json, "$.store..price"),
+        sorted(vec![json!(399), json!(8.95), json!(12.99), json!(8.99), json!(22.99)])
+    );
+    assert_eq!(select(&json, "$..book[2]"), vec![json["store"]["book"][2].clone()]);
+    assert_eq!(select(&json, "$..book[2].author"), vec![json!("Herman Melville")]);
+    assert_eq!(select(&json, "$..book[2].publisher"), vec![]);
+    assert_eq!(select(&json, "$..book[-1]"), vec![json["store"]["book"][3].clone()]);
+    assert_eq!(select(&json, "$..book[0,1]"), select(&json, "$..book[:2]"));
+    assert_eq!(select(&json, "$..book[0,1]").len(), 2);
+    assert_eq!(
+        select(&json, "$..book[?@.isbn].title"),
+        vec![json!("Moby Dick"), json!("The Lord of the Rings")]
+    );
+    assert_eq!(
+        select(&json, "$..book[?@.price<10].title"),
+        vec![json!("Sayings of the Century"), json!("Moby Dick")]
+    );
+    assert_eq!(select(&json, "$..*").len(), 27);
+}
+
+#[test]
+fn name_selector()
+{
+    let json = json!({"o": {"j j": {"k.k": 3}}, "'": {"@": 2}});
+
+    assert_eq!(select(&json, "$.o['j j']"), vec![json!({"k.k": 3})]);
+    assert_eq!(select(&json, "$.o['j j']['k.k']"), vec![json!(3)]);
+    assert_eq!(select(&json, "$.o[\"j j\"][\"k.k\"]"), vec![json!(3)]);
+    assert_eq!(select(&json, "$[\"'\"][\"@\"]"), vec![json!(2)]);
+    assert_eq!(select(&json, "$['\\'']['\\u0040']"), vec![json!(2)]);
+}
+
+#[test]
+fn wildcard_selector()
+{
+    let json = json!({"o": {"j": 1, "k": 2}, "a": [5, 3]});
+
+    assert_eq!(select_sorted(&json, "$[*]"), sorted(vec![json!({"j": 1, "k": 2}), json!([5, 3])]));
+    assert_eq!(select_sorted(&json, "$.o[*]"), vec![json!(1), json!(2)]);
+    assert_eq!(select_sorted(&json, "$.o[*, *]"), vec![json!(1), json!(1), json!(2), json!(2)]);
+    assert_eq!(select(&json, "$.a[*]"), vec![json!(5), json!(3)]);
+}
+
+#[test]
+fn index_selector()
+{
+    let json = json!(["a", "b"]);
+
+    assert_eq!(select(&json, "$[1]"), vec![json!("b")]);
+    assert_eq!(select(&json, "$[-2]"), vec![json!("a")]);
+    assert_eq!(select(&json, "$[2]"), vec![]);
+    assert_eq!(select(&json, "$[-3]"), vec![]);
+    assert_eq!(select(&json, "$.a[0]"), vec![]);
+}
+
+#[test]
+fn slice_selector()
+{
+    let json = json!(["a", "b", "c", "d", "e", "f", "g"]);
+
+    assert_eq!(select(&json, "$[1:3]"), vec![json!("b"), json!("c")]);
+    assert_eq!(select(&json, "$[5:]"), vec![json!("f"), json!("g")]);
+    assert_eq!(select(&json, "$[1:5:2]"), vec![json!("b"), json!("d")]);
+    assert_eq!(select(&json, "$[5:1:-2]"), vec![json!("f"), json!("d")]);
+    assert_eq!(
+        select(&json, "$[::-1]"),
+        vec![json!("g"), json!("f"), json!("e"), json!("d"), json!("c"), json!("b"), json!("a")]
+    );
+    assert_eq!(select(&json, "$[-2:]"), vec![json!("f"), json!("g")]);
+    assert_eq!(select(&json, "$[::0]"), vec![]);
+    assert_eq!(select(&json, "$[-100:100:3]"), vec![json!("a"), json!("d"), json!("g")]);
+    assert_eq!(select(&json, "$[ 1 : 2 : 1 ]"), vec![json!("b")]);
+}
+
+fn filter_example() -> Json
+{
+    json!({
+        "a": [3, 5, 1, 2, 4, 6, {"b": "j"}, {"b": "k"}, {"b": {}}, {"b": "kilo"}],
+        "o": {"p": 1, "q": 2, "r": 3, "s": 5, "t": {"u": 6}},
+        "e": "f"
+    })
+}
+
+#[test]
+fn filter_selector()
+{
+    let json = filter_example();
+
+    assert_eq!(select(&json, "$.a[?@.b == 'kilo']"), vec![json!({"b": "kilo"})]);
+    assert_eq!(select(&json, "$.a[?(@.b == 'kilo')]"), vec![json!({"b": "kilo"})]);
+    assert_eq!(select(&json, "$.a[?@>3.5]"), vec![json!(5), json!(4), json!(6)]);
+    assert_eq!(
+        select(&json, "$.a[?@.b]"),
+        vec![json!({"b": "j"}), json!({"b": "k"}), json!({"b": {}}), json!({"b": "kilo"})]
+    );
+    assert_eq!(select_sorted(&json, "$[?@.*]"), sorted(vec![json["a"].clone(), json["o"].clone()]));
+    assert_eq!(select(&json, "$[?@[?@.b]]"), vec![json["a"].clone()]);
+    assert_eq!(select_sorted(&json, "$.o[?@<3, ?@<3]"), vec![json!(1), json!(1), json!(2), json!(2)]);
+    assert_eq!(select(&json, "$.a[?@<2 || @.b == \"k\"]"), vec![json!(1), json!({"b": "k"})]);
+    assert_eq!(select(&json, "$.a[?match(@.b, \"[jk]\")]"), vec![json!({"b": "j"}), json!({"b": "k"})]);
+    assert_eq!(
+        select(&json, "$.a[?search(@.b, \"[jk]\")]"),
+        vec![json!({"b": "j"}), json!({"b": "k"}), json!({"b": "kilo"})]
+    );
+    assert_eq!(select_sorted(&json, "$.o[?@>1 && @<4]"), vec![json!(2), json!(3)]);
+    assert_eq!(select(&json, "$.o[?@.u || @.x]"), vec![json!({"u": 6})]);
+    assert_eq!(
+        select(&json, "$.a[?@.b == $.x]"),
+        vec![json!(3), json!(5), json!(1), json!(2), json!(4), json!(6)]
+    );
+    assert_eq!(select(&json, "$.a[?@ == @]").len(), 10);
+    assert_eq!(select(&json, "$.a[?!@.b]").len(), 6);
+    assert_eq!(select(&json, "$.a[?!(@ > 2 && @ < 6)]"), vec![
+        json!(1), json!(2), json!(6), json!({"b": "j"}), json!({"b": "k"}), json!({"b": {}}), json!({"b": "kilo"}),
+    ]);
+}
+
+#[test]
+fn comparisons()
+{
+    let json = json!({"obj": {"x": "y"}, "arr": [2, 3]});
+
+    let holds = |expression: &str| {
+        match select(&json, &format!("$[?{}]", expression)).len() {
+            2 => true,
+            0 => false,
+            _ => panic!("{}", expression),
+        }
+    };
+
+    assert!(holds("$.absent1 == $.absent2"));
+    assert!(holds("$.absent1 <= $.absent2"));
+    assert!(!holds("$.absent == 'g'"));
+    assert!(!holds("$.absent1 != $.absent2"));
+    assert!(holds("$.absent != 'g'"));
+    assert!(holds("1 <= 2"));
+    assert!(!holds("1 > 2"));
+    assert!(!holds("13 == '13'"));
+    assert!(holds("'a' <= 'b'"));
+    assert!(!holds("'a' > 'b'"));
+    assert!(!holds("$.obj == $.arr"));
+    assert!(holds("$.obj != $.arr"));
+    assert!(holds("$.obj == $.obj"));
+    assert!(!holds("$.obj != $.obj"));
+    assert!(holds("$.arr == $.arr"));
+    assert!(!holds("$.arr != $.arr"));
+    assert!(!holds("$.obj == 17"));
+    assert!(holds("$.obj != 17"));
+    assert!(!holds("$.obj <= $.arr"));
+    assert!(!holds("$.obj < $.arr"));
+    assert!(holds("$.obj <= $.obj"));
+    assert!(holds("$.arr <= $.arr"));
+    assert!(!holds("1 <= $.arr"));
+    assert!(!holds("1 >= $.arr"));
+    assert!(!holds("1 > $.arr"));
+    assert!(!holds("1 < $.arr"));
+    assert!(holds("true <= true"));
+    assert!(!holds("true > true"));
+
+    // Numbers compare by value.
+    assert!(holds("1 == 1.0"));
+    assert!(holds("$.arr[0] == 2e0"));
+    assert!(holds("-1 < 0"));
+    assert!(holds("'\u{e9}' > 'z'"));
+}
+
+#[test]
+fn functions()
+{
+    let json = json!([
+        {"name": "Alice", "tags": ["a", "b", "c"], "timezone": "Europe/Paris", "color": {"car": "red"}},
+        {"name": "Bob", "tags": [], "timezone": "America/New_York", "color": ["blue", "red"]},
+        {"name": "\u{e9}l\u{e8}ve", "tags": {"x": 1}, "timezone": "Europe\nBerlin"}
+    ]);
+
+    assert_eq!(select(&json, "$[?length(@.name) == 5].name"), vec![json!("Alice"), json!("\u{e9}l\u{e8}ve")]);
+    assert_eq!(select(&json, "$[?length(@.tags) >= 1].name"), vec![json!("Alice"), json!("\u{e9}l\u{e8}ve")]);
+    assert_eq!(select(&json, "$[?length(@.missing) == 0]"), vec![]);
+    assert_eq!(select(&json, "$[?length(1) == 0]"), vec![]);
+    assert_eq!(select(&json, "$[?count(@.tags.*) == 3].name"), vec![json!("Alice")]);
+    assert_eq!(select(&json, "$[?count(@..*) > 7].name"), vec![json!("Alice")]);
+    assert_eq!(select(&json, "$[?match(@.timezone, 'Europe/.*')].name"), vec![json!("Alice")]);
+    assert_eq!(select(&json, "$[?search(@.timezone, 'Europe')].name").len(), 2);
+    assert_eq!(select(&json, "$[?match(@.timezone, 'Europe')]"), vec![]);
+    assert_eq!(select(&json, "$[?value(@..car) == 'red'].name"), vec![json!("Alice")]);
+    assert_eq!(select(&json, "$[?value(@.color.*) == 'red'].name"), vec![json!("Alice")]);
+    assert_eq!(select(&json, "$[?match(@.name, @.name)]").len(), 3);
+    assert_eq!(select(&json, "$[?!match(@.name, 'A.*')].name"), vec![json!("Bob"), json!("\u{e9}l\u{e8}ve")]);
+    assert_eq!(select(&json, "$[?length(value(@.tags)) == 0].name"), vec![json!("Bob")]);
+}
+
+#[test]
+fn regular_expressions()
+{
+    let json = json!(["abc", "ABC", "a-c", "a\nc", "12", "\u{c9}t\u{e9}", "aaa", "x|y", ""]);
+
+    let matching = |pattern: &str| -> Vec<Json> {
+        select(&json, &format!("$[?match(@, '{}')]", pattern.replace('\\', "\\\\").replace('\'', "\\'")))
+    };
+
+    assert_eq!(matching("a.c"), vec![json!("abc"), json!("a-c")]);
+    assert_eq!(matching("[a-c]+"), vec![json!("abc"), json!("aaa")]);
+    assert_eq!(matching("[^a-z]*"), vec![json!("ABC"), json!("12"), json!("")]);
+    assert_eq!(matching("a[-b]c"), vec![json!("abc"), json!("a-c")]);
+    assert_eq!(matching("a{3}|[0-9]{1,2}"), vec![json!("12"), json!("aaa")]);
+    assert_eq!(matching("a{2,}"), vec![json!("aaa")]);
+    assert_eq!(matching("(ab|a-)c?"), vec![json!("abc"), json!("a-c")]);
+    assert_eq!(matching("x\\|y"), vec![json!("x|y")]);
+    assert_eq!(matching("a\\nc"), vec![json!("a\nc")]);
+    assert_eq!(matching("\\p{Lu}+"), vec![json!("ABC")]);
+    assert_eq!(matching("\\p{L}+"), vec![json!("abc"), json!("ABC"), json!("\u{c9}t\u{e9}"), json!("aaa")]);
+    assert_eq!(matching("\\P{L}+"), vec![json!("12")]);
+    assert_eq!(matching("[\\p{Nd}]+"), vec![json!("12")]);
+    assert_eq!(matching("a\\p{Pd}c"), vec![json!("a-c")]);
+    assert_eq!(matching("a\\p{Cc}c"), vec![json!("a\nc")]);
+    assert_eq!(matching("a\\P{Cs}c"), vec![json!("abc"), json!("a-c"), json!("a\nc")]);
+    assert_eq!(matching("\\p{Cs}"), vec![]);
+    assert_eq!(matching(""), vec![json!("")]);
+    assert_eq!(matching("(a|)*b?c?"), vec![json!("abc"), json!("aaa"), json!("")]);
+
+    // Invalid patterns match nothing.
+    assert_eq!(matching("a{2,1}"), vec![]);
+    assert_eq!(matching("[z-a]"), vec![]);
+    assert_eq!(matching("\\d"), vec![]);
+    assert_eq!(matching("(a"), vec![]);
+    assert_eq!(matching("a]"), vec![]);
+    assert_eq!(matching("\\p{Xx}"), vec![]);
+
+    // Counts past the size limit are invalid rather than compiled, and
+    // repeating an empty group stays cheap whatever the count.
+    assert_eq!(matching("(){3}"), vec![json!("")]);
+    assert_eq!(matching("(){99999999999999}"), vec![]);
+    assert_eq!(matching("a{10001}"), vec![]);
+    assert_eq!(matching("((){10000}){10000}"), vec![json!("")]);
+
+    // Searching finds patterns anywhere.
+    assert_eq!(select(&json, "$[?search(@, 'b')]"), vec![json!("abc")]);
+    assert_eq!(select(&json, "$[?search(@, '^a')]"), vec![]);
+    assert_eq!(select(&json, "$[?search(@, '')]").len(), 9);
+}
+
+#[test]
+fn descendant_segment()
+{
+    let json = json!({"o": {"j": 1, "k": 2}, "a": [5, 3, [{"j": 4}, {"k": 6}]]});
+
+    assert_eq!(select_sorted(&json, "$..j"), vec![json!(1), json!(4)]);
+    assert_eq!(select(&json, "$..[0]"), vec![json!(5), json!({"j": 4})]);
+    assert_eq!(select(&json, "$..[*]").len(), 11);
+    assert_eq!(select_sorted(&json, "$..*"), select_sorted(&json, "$..[*]"));
+    assert_eq!(select(&json, "$..o"), vec![json!({"j": 1, "k": 2})]);
+    assert_eq!(select_sorted(&json, "$.o..[*, *]"), vec![json!(1), json!(1), json!(2), json!(2)]);
+    assert_eq!(
+        paths(&json, "$.a..[0, 1]"),
+        vec!["$['a'][0]", "$['a'][1]", "$['a'][2][0]", "$['a'][2][1]"]
+    );
+}
+
+#[test]
+fn null_semantics()
+{
+    let json = json!({"a": null, "b": [null], "c": [{}], "null": 1});
+
+    assert_eq!(select(&json, "$.a"), vec![json!(null)]);
+    assert_eq!(select(&json, "$.a[0]"), vec![]);
+    assert_eq!(select(&json, "$.a.d"), vec![]);
+    assert_eq!(select(&json, "$.b[0]"), vec![json!(null)]);
+    assert_eq!(select(&json, "$.b[*]"), vec![json!(null)]);
+    assert_eq!(select(&json, "$.b[?@]"), vec![json!(null)]);
+    assert_eq!(select(&json, "$.b[?@==null]"), vec![json!(null)]);
+    assert_eq!(select(&json, "$.c[?@.d==null]"), vec![]);
+    assert_eq!(select(&json, "$.null"), vec![json!(1)]);
+}
+
+#[test]
+fn normalized_paths()
+{
+    let json = json!({"a": {"b": [0, 1, 2]}, "\u{b}": 1, "it's": {"\\": 2}});
+
+    assert_eq!(paths(&json, "$"), vec!["$"]);
+    assert_eq!(paths(&json, "$.a"), vec!["$['a']"]);
+    assert_eq!(paths(&json, "$.a.b[-1]"), vec!["$['a']['b'][2]"]);
+    assert_eq!(paths(&json, "$.a.b[1:2]"), vec!["$['a']['b'][1]"]);
+    assert_eq!(paths(&json, "$[\"\\u000B\"]"), vec!["$['\\u000b']"]);
+    assert_eq!(paths(&json, "$[\"\\u0061\"]"), vec!["$['a']"]);
+    assert_eq!(paths(&json, "$[\"it's\"]['\\\\']"), vec!["$['it\\'s']['\\\\']"]);
+
+    let path  = JsonPath::parse("$.a.b[?@ > 0]").unwrap();
+    let found = path.query(&json);
+
+    assert_eq!(found.iter().map(|found| found.value).collect::<Vec<_>>(), vec![&json!(1), &json!(2)]);
+    assert_eq!(found[1].pointer().to_string(), "/a/b/2");
+    assert_eq!(json.pointer(&found[1].pointer().to_string()), Some(found[1].value));
+}
+
+#[test]
+fn blank_space()
+{
+    let json = json!({"a": [{"b": 1}, {"b": 2}]});
+
+    assert_eq!(select(&json, "$ .a [ ?@.b == 2 ] .b"), vec![json!(2)]);
+    assert_eq!(select(&json, "$.a[?\n@.b\t==\r1 ]"), vec![json!({"b": 1})]);
+    assert_eq!(select(&json, "$.a[?( @.b>1 )||( @.b<1 )]"), vec![json!({"b": 2})]);
+    assert_eq!(select(&json, "$['a' , 'a'][0]"), vec![json!({"b": 1}), json!({"b": 1})]);
+}
+
+fn error_at(query: &str, column: usize, error: Error) -> Result<JsonPath, Error>
+{
+    let position = Position { line: 1, column, offset: query.char_indices().nth(column - 1).map_or(query.len(), |(i, _)| i) };
+
+    Err(Error::Syntax(position, Box::new(error)))
+}
+
+fn character(chr: &str) -> Error
+{
+    Error::InvalidCharacter(String::from(chr))
+}
+
+#[test]
+fn syntax_errors()
+{
+    let invalid = [
+        "", "a", " $", "$ ", "$.", "$..", "$.[a]", "$[", "$[]", "$[a]", "$['a'", "$['a\"]", "$[\"a']",
+        "$['\\\"']", "$[\"\\'\"]", "$['\\x']", "$['\\uD800']", "$['\u{1}']", "$[01]", "$[-0]", "$[- 1]",
+        "$[1 2]", "$[::1:]", "$.1a", "$.a.", "$..[", "$[?]", "$[?@ ==]", "$[?1]", "$[?true]", "$[?'a']",
+        "$[?@.a == 1 == 2]", "$[?@.a = 1]", "$[?!@.a == 1]", "$[?(@.a]", "$[?@.a &&]", "$[?@.a || ]",
+        "$[?@ == 01]", "$[?@ == 1.]", "$[?@ == True]", "$[?@ == nul]", "$[?@.a.* == 1]", "$[?@..a == 1]",
+        "$[?@[0:1] == 1]", "$[?@['a', 'b'] == 1]", "$[?@[*] == 1]",
+    ];
+
+    for query in invalid.iter() {
+        assert!(JsonPath::parse(query).is_err(), "{:?}", query);
+    }
+
+    assert_eq!(JsonPath::parse("$[01]"), error_at("$[01]", 4, character("1")));
+    assert_eq!(JsonPath::parse("$.a "), error_at("$.a ", 4, character(" ")));
+    assert_eq!(JsonPath::parse("$['a'"), error_at("$['a'", 6, Error::UnexpectedEof));
+    assert_eq!(JsonPath::parse("$[?@ == nul]"), error_at("$[?@ == nul]", 9, character("nul")));
+    assert_eq!(
+        JsonPath::parse("$[9007199254740992]"),
+        error_at("$[9007199254740992]", 3, Error::OutOfRange(String::from("9007199254740992"), "index"))
+    );
+    assert!(JsonPath::parse("$[9007199254740991, -9007199254740991]").is_ok());
+}
+
+#[test]
+fn type_errors()
+{
+    let valid = [
+        "$[?length(@) < 3]",
+        "$[?count(@.*) > 1]",
+        "$[?match(@.timezone, 'Europe/.*')]",
+        "$[?value(@..color) == \"red\"]",
+        "$[?length(value(@.a)) == 1]",
+        "$[?count(@..a) == length(@.b)]",
+        "$[?search(@.a, $.pattern)]",
+    ];
+
+    for query in valid.iter() {
+        assert!(JsonPath::parse(query).is_ok(), "{:?}", query);
+    }
+
+    assert_eq!(
+        JsonPath::parse("$[?length(@.*) < 3]"),
+        error_at("$[?length(@.*) < 3]", 11, Error::InvalidType("value", "nodes"))
+    );
+    assert_eq!(
+        JsonPath::parse("$[?count(1) == 1]"),
+        error_at("$[?count(1) == 1]", 10, Error::InvalidType("nodes", "value"))
+    );
+    assert_eq!(
+        JsonPath::parse("$[?count(foo(@.*)) > 1]"),
+        error_at("$[?count(foo(@.*)) > 1]", 10, character("foo"))
+    );
+    assert_eq!(
+        JsonPath::parse("$[?match(@.timezone, 'Europe/.*') == true]"),
+        error_at("$[?match(@.timezone, 'Europe/.*') == true]", 4, Error::InvalidType("value", "logical"))
+    );
+    assert_eq!(
+        JsonPath::parse("$[?value(@..color)]"),
+        error_at("$[?value(@..color)]", 4, Error::InvalidType("logical", "value"))
+    );
+    assert_eq!(
+        JsonPath::parse("$[?length(@.a, @.b) == 1]"),
+        error_at("$[?length(@.a, @.b) == 1]", 4, Error::InvalidLength(1, 2))
+    );
+    assert_eq!(
+        JsonPath::parse("$[?match(@.a) == 1]"),
+        error_at("$[?match(@.a) == 1]", 4, Error::InvalidLength(2, 1))
+    );
+    assert!(JsonPath::parse("$[?length(@.a == 1) == 1]").is_err());
+    assert!(JsonPath::parse("$[?length(@.a) == count(@.*) == 1]").is_err());
+}
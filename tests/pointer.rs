@@ -0,0 +1,138 @@
+#[macro_use]
+extern crate simple_json;
+use simple_json::{ Error, Json, JsonPointer };
+
+fn rfc_example() -> Json
+{
+    Json::parse(r#"{
+        "foo": ["bar", "baz"],
+        "": 0,
+        "a/b": 1,
+        "c%d": 2,
+        "e^f": 3,
+        "g|h": 4,
+        "i\\j": 5,
+        "k\"l": 6,
+        " ": 7,
+        "m~n": 8
+    }"#).unwrap()
+}
+
+#[test]
+fn rfc_6901()
+{
+    let json = rfc_example();
+
+    assert_eq!(json.pointer(""), Some(&json));
+    assert_eq!(json.pointer("/foo"), Some(&json!(["bar", "baz"])));
+    assert_eq!(json.pointer("/foo/0"), Some(&json!("bar")));
+    assert_eq!(json.pointer("/"), Some(&json!(0)));
+    assert_eq!(json.pointer("/a~1b"), Some(&json!(1)));
+    assert_eq!(json.pointer("/c%d"), Some(&json!(2)));
+    assert_eq!(json.pointer("/e^f"), Some(&json!(3)));
+    assert_eq!(json.pointer("/g|h"), Some(&json!(4)));
+    assert_eq!(json.pointer("/i\\j"), Some(&json!(5)));
+    assert_eq!(json.pointer("/k\"l"), Some(&json!(6)));
+    assert_eq!(json.pointer("/ "), Some(&json!(7)));
+    assert_eq!(json.pointer("/m~0n"), Some(&json!(8)));
+}
+
+#[test]
+fn missing()
+{
+    let json = rfc_example();
+
+    assert_eq!(json.pointer("/foo/2"), None);
+    assert_eq!(json.pointer("/foo/-"), None);
+    assert_eq!(json.pointer("/foo/01"), None);
+    assert_eq!(json.pointer("/foo/+1"), None);
+    assert_eq!(json.pointer("/foo/0/bar"), None);
+    assert_eq!(json.pointer("/bar"), None);
+
+    // Not pointers at all.
+    assert_eq!(json.pointer("foo"), None);
+    assert_eq!(json.pointer("/m~2n"), None);
+    assert_eq!(json.pointer("/m~"), None);
+}
+
+#[test]
+fn parse_and_display()
+{
+    let pointer: JsonPointer = "/a~1b/m~0n/0".parse().unwrap();
+
+    assert_eq!(pointer.segments(), ["a/b", "m~n", "0"]);
+    assert_eq!(pointer.to_string(), "/a~1b/m~0n/0");
+
+    assert_eq!(JsonPointer::parse(""), Ok(JsonPointer::new()));
+    assert_eq!(JsonPointer::parse("/").unwrap().segments(), [""]);
+    assert_eq!(JsonPointer::parse("a"), Err(Error::InvalidPath(String::from("a"))));
+    assert_eq!(JsonPointer::parse("/~x"), Err(Error::InvalidPath(String::from("/~x"))));
+}
+
+#[test]
+fn build()
+{
+    let mut pointer = JsonPointer::new().with("servers").with(0.to_string());
+    pointer.push("host/name");
+
+    assert_eq!(pointer.to_string(), "/servers/0/host~1name");
+    assert_eq!(pointer.pop(), Some(String::from("host/name")));
+    assert!(!pointer.is_root());
+
+    let json = json!({"servers": [{"port": 80}]});
+
+    assert_eq!(pointer.with("port").get(&json), Some(&json!(80)));
+    assert_eq!(vec!["servers", "1"].into_iter().collect::<JsonPointer>().get(&json), None);
+}
+
+#[test]
+fn pointer_mut()
+{
+    let mut json = json!({"servers": [{"port": 80}]});
+
+    *json.pointer_mut("/servers/0/port").unwrap() = json!(8080);
+
+    assert_eq!(json, json!({"servers": [{"port": 8080}]}));
+    assert!(json.pointer_mut("/servers/1").is_none());
+}
+
+#[test]
+fn insert()
+{
+    let mut json = json!({"list": [1, 3], "name": "a"});
+
+    assert_eq!(json.pointer_insert("/list/1", json!(2)), Ok(None));
+    assert_eq!(json.pointer_insert("/list/-", json!(4)), Ok(None));
+    assert_eq!(json.pointer_insert("/list/4", json!(5)), Ok(None));
+    assert_eq!(json.pointer_insert("/name", json!("b")), Ok(Some(json!("a"))));
+    assert_eq!(json.pointer_insert("/new", json!(null)), Ok(None));
+
+    assert_eq!(json, json!({"list": [1, 2, 3, 4, 5], "name": "b", "new": null}));
+
+    let invalid = |path: &str| Err(Error::InvalidPath(String::from(path)));
+
+    assert_eq!(json.pointer_insert("/list/6", json!(0)), invalid("/list/6"));
+    assert_eq!(json.pointer_insert("/list/x", json!(0)), invalid("/list/x"));
+    assert_eq!(json.pointer_insert("/name/x", json!(0)), invalid("/name/x"));
+    assert_eq!(json.pointer_insert("/a/b", json!(0)), invalid("/a/b"));
+
+    assert_eq!(json.pointer_insert("", json!([])), Ok(Some(json!({"list": [1, 2, 3, 4, 5], "name": "b", "new": null}))));
+    assert_eq!(json, json!([]));
+}
+
+#[test]
+fn remove()
+{
+    let mut json = json!({"list": [1, 2, 3], "a/b": {"c": true}});
+
+    assert_eq!(json.pointer_remove("/list/1"), Ok(json!(2)));
+    assert_eq!(json.pointer_remove("/a~1b/c"), Ok(json!(true)));
+    assert_eq!(json, json!({"list": [1, 3], "a/b": {}}));
+
+    let invalid = |path: &str| Err(Error::InvalidPath(String::from(path)));
+
+    assert_eq!(json.pointer_remove("/list/2"), invalid("/list/2"));
+    assert_eq!(json.pointer_remove("/list/-"), invalid("/list/-"));
+    assert_eq!(json.pointer_remove("/missing"), invalid("/missing"));
+    assert_eq!(json.pointer_remove(""), invalid(""));
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+extern crate simple_json;
+use simple_json::{ Format, Json };
+
+// Object entries come out in HashMap order, so compare lines regardless of
+// their order and of the separating commas.
+fn lines(text: &str) -> Vec<String>
+{
+    let mut lines: Vec<String> = text.lines()
+        .map(|line| String::from(line.trim_end_matches(',')))
+        .collect();
+
+    lines.sort();
+    lines
+}
+
+#[test]
+fn readme_example()
+{
+    let text   = "{ \"integer\": 12, \"float\": 80.5, \"string\": \"A JSON sample\", \"array\": [ 1, 2, 3 ], \"object\": { \"a\": \"b\" } }";
+    let result = Json::parse(text).unwrap();
+
+    let expect = "{
+  \"integer\": 12,
+  \"float\":   80.5,
+  \"string\":  \"A JSON sample\",
+  \"array\": [ 1, 2, 3 ],
+  \"object\": {
+    \"a\": \"b\"
+  }
+}";
+
+    assert_eq!(lines(&result.to_pretty_string()), lines(expect));
+    assert!(result.to_pretty_string().starts_with("{\n  \""));
+    assert!(result.to_pretty_string().ends_with("\n}"));
+
+    assert_eq!(Json::parse(&result.to_pretty_string()), Ok(result));
+}
+
+#[test]
+fn nested_arrays()
+{
+    let json = Json::parse("[[], {}, [1, [2]], [true, null, \"x\"]]").unwrap();
+
+    assert_eq!(json.to_pretty_string(), "[
+  [],
+  {},
+  [
+    1,
+    [ 2 ]
+  ],
+  [ true, null, \"x\" ]
+]");
+}
+
+#[test]
+fn inline_array_limit()
+{
+    let json = Json::parse("[1, 2, 3]").unwrap();
+
+    let format = Format { inline_arrays: 2, ..Format::pretty() };
+    assert_eq!(json.to_string_with(&format).unwrap(), "[\n  1,\n  2,\n  3\n]");
+
+    let format = Format { inline_arrays: 3, ..Format::pretty() };
+    assert_eq!(json.to_string_with(&format).unwrap(), "[ 1, 2, 3 ]");
+}
+
+#[test]
+fn custom_format()
+{
+    let mut map = HashMap::new();
+    map.insert(String::from("key"), Json::Array(vec![Json::from(1)]));
+
+    let json = Json::Array(vec![Json::Object(map)]);
+
+    let format = Format {
+        indent:            Some(String::from("\t")),
+        newline:           String::from("\r\n"),
+        space_after_colon: false,
+        inline_arrays:     0,
+        ..Format::pretty()
+    };
+
+    assert_eq!(
+        json.to_string_with(&format).unwrap(),
+        "[\r\n\t{\r\n\t\t\"key\":[\r\n\t\t\t1\r\n\t\t]\r\n\t}\r\n]"
+    );
+}
+
+#[test]
+fn alignment()
+{
+    let mut map = HashMap::new();
+    map.insert(String::from("a"), Json::from(1));
+    map.insert(String::from("long"), Json::from(2));
+
+    let json = Json::Object(map);
+
+    let aligned = json.to_pretty_string();
+    assert!(aligned.contains("\n  \"a\":    1"));
+    assert!(aligned.contains("\n  \"long\": 2"));
+
+    let format = Format { align_values: false, ..Format::pretty() };
+    let plain  = json.to_string_with(&format).unwrap();
+    assert!(plain.contains("\n  \"a\": 1"));
+    assert!(plain.contains("\n  \"long\": 2"));
+}
+
+#[test]
+fn compact_is_default()
+{
+    let json = Json::parse("{\"a\": [1, 2]}").unwrap();
+
+    assert_eq!(json.to_string(), "{\"a\":[1,2]}");
+
+    let format = Format { space_after_colon: true, ..Format::default() };
+    assert_eq!(json.to_string_with(&format).unwrap(), "{\"a\": [1,2]}");
+}
@@ -0,0 +1,107 @@
+use std::cmp::Ordering;
+
+extern crate simple_json;
+use simple_json::{ Format, Json, KeyOrder };
+
+fn sorted(order: KeyOrder) -> Format
+{
+    Format { sort_keys: order, ..Format::new() }
+}
+
+#[test]
+fn lexicographic_compact()
+{
+    let json = Json::parse("{\"b\": 1, \"a\": {\"d\": 2, \"c\": [{\"f\": 3, \"e\": 4}]}, \"B\": 5}").unwrap();
+
+    assert_eq!(
+        json.to_string_with(&sorted(KeyOrder::Lexicographic)).unwrap(),
+        "{\"B\":5,\"a\":{\"c\":[{\"e\":4,\"f\":3}],\"d\":2},\"b\":1}"
+    );
+}
+
+#[test]
+fn lexicographic_pretty()
+{
+    let text = "{ \"integer\": 12, \"float\": 80.5, \"string\": \"A JSON sample\", \"array\": [ 1, 2, 3 ], \"object\": { \"a\": \"b\" } }";
+    let json = Json::parse(text).unwrap();
+
+    let format = Format { sort_keys: KeyOrder::Lexicographic, ..Format::pretty() };
+
+    assert_eq!(json.to_string_with(&format).unwrap(), "{
+  \"array\": [ 1, 2, 3 ],
+  \"float\":   80.5,
+  \"integer\": 12,
+  \"object\": {
+    \"a\": \"b\"
+  },
+  \"string\":  \"A JSON sample\"
+}");
+}
+
+#[test]
+fn natural()
+{
+    let json = Json::parse("{\"item10\": 0, \"item2\": 0, \"item1\": 0, \"item02\": 0, \"item\": 0, \"a10b2\": 0, \"a10b10\": 0, \"a9\": 0}").unwrap();
+
+    assert_eq!(
+        json.to_string_with(&sorted(KeyOrder::Natural)).unwrap(),
+        "{\"a9\":0,\"a10b2\":0,\"a10b10\":0,\"item\":0,\"item1\":0,\"item2\":0,\"item02\":0,\"item10\":0}"
+    );
+}
+
+#[test]
+fn natural_large_numbers()
+{
+    let json = Json::parse("{\"99999999999999999999999\": 0, \"100000000000000000000000\": 0, \"5\": 0}").unwrap();
+
+    assert_eq!(
+        json.to_string_with(&sorted(KeyOrder::Natural)).unwrap(),
+        "{\"5\":0,\"99999999999999999999999\":0,\"100000000000000000000000\":0}"
+    );
+}
+
+#[test]
+fn custom()
+{
+    fn by_length(a: &str, b: &str) -> Ordering
+    {
+        a.len().cmp(&b.len()).then_with(|| b.cmp(a))
+    }
+
+    let json = Json::parse("{\"ccc\": 0, \"a\": 0, \"bb\": 0, \"b\": 0}").unwrap();
+
+    assert_eq!(
+        json.to_string_with(&sorted(KeyOrder::custom(by_length))).unwrap(),
+        "{\"b\":0,\"a\":0,\"bb\":0,\"ccc\":0}"
+    );
+}
+
+#[test]
+fn custom_with_captures()
+{
+    let priority: Vec<String> = "id,name".split(',').map(String::from).collect();
+    let order    = KeyOrder::custom(move |a: &str, b: &str| {
+        let rank = |key| priority.iter().position(|first| *first == key).unwrap_or(priority.len());
+
+        rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
+    });
+
+    let json = Json::parse("{\"b\": 0, \"name\": 0, \"a\": 0, \"id\": 0}").unwrap();
+
+    assert_eq!(json.to_string_with(&sorted(order.clone())).unwrap(), "{\"id\":0,\"name\":0,\"a\":0,\"b\":0}");
+    assert_eq!(order, order.clone());
+    assert!(order != KeyOrder::custom(|a, b| a.cmp(b)));
+}
+
+#[test]
+fn deterministic()
+{
+    let text = "{\"z\": 1, \"y\": 2, \"x\": 3, \"w\": 4, \"v\": 5, \"u\": 6, \"t\": 7}";
+    let format = sorted(KeyOrder::Lexicographic);
+
+    let first = Json::parse(text).unwrap().to_string_with(&format).unwrap();
+
+    for _ in 0..10 {
+        assert_eq!(Json::parse(text).unwrap().to_string_with(&format).unwrap(), first);
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+extern crate simple_json;
+use simple_json::{ Json, Number, Tape };
+
+const TEXT: &str = "{ \"name\": \"tape\", \"size\": { \"x\": 800, \"y\": -600 }, \"tags\": [ \"a\", [], {}, null, true, 2.5 ] }";
+
+#[test]
+fn parse_and_navigate()
+{
+    let tape = Tape::parse(TEXT).unwrap();
+    let root = tape.root();
+
+    assert!(root.is_object());
+    assert_eq!(root.len(), 3);
+    assert_eq!(root.get("name").unwrap().as_str(), Some("tape"));
+    assert_eq!(root.get("size").unwrap().get("x").unwrap().as_number(), Some(&Number::Unsigned(800)));
+    assert_eq!(root.get("size").unwrap().get("y").unwrap().as_number(), Some(&Number::Integer(-600)));
+    assert!(root.get("missing").is_none());
+
+    let tags = root.get("tags").unwrap();
+
+    assert!(tags.is_array());
+    assert_eq!(tags.len(), 6);
+    assert_eq!(tags.at(0).unwrap().as_str(), Some("a"));
+    assert!(tags.at(1).unwrap().is_array());
+    assert!(tags.at(2).unwrap().is_object());
+    assert!(tags.at(3).unwrap().is_null());
+    assert_eq!(tags.at(4).unwrap().as_bool(), Some(true));
+    assert_eq!(tags.at(5).unwrap().as_number(), Some(&Number::Float(2.5)));
+    assert!(tags.at(6).is_none());
+
+    let keys: Vec<&str> = root.entries().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec!["name", "size", "tags"]);
+}
+
+#[test]
+fn matches_json_parse()
+{
+    let tape = Tape::parse(TEXT).unwrap();
+
+    assert_eq!(tape.to_json(), Json::parse(TEXT).unwrap());
+    assert_eq!(Json::from(&tape), Json::parse(TEXT).unwrap());
+}
+
+#[test]
+fn json_round_trip()
+{
+    let mut size = HashMap::new();
+    size.insert(String::from("x"), Json::from(800_u64));
+    size.insert(String::from("y"), Json::from("six hundred"));
+
+    let json = Json::Array(vec![Json::Object(size), Json::Null, Json::Array(vec![])]);
+    let tape = Tape::from(&json);
+
+    assert_eq!(tape.root().len(), 3);
+    assert_eq!(tape.root().at(0).unwrap().get("y").unwrap().as_str(), Some("six hundred"));
+    assert_eq!(Json::from(tape), json);
+}
+
+#[test]
+fn invalid_documents()
+{
+    assert!(Tape::parse("").is_err());
+    assert!(Tape::parse("[1, 2").is_err());
+    assert!(Tape::parse("{\"a\" 1}").is_err());
+    assert!(Tape::parse("[1] 2").is_err());
+    assert!(Tape::parse("[\"a\tb\"]").is_err());
+    assert!(Tape::parse("[ ]").is_ok());
+}
+
+#[test]
+fn interned_keys()
+{
+    let mut text = String::from("[");
+
+    for i in 0..1000 {
+        if i > 0 {
+            text.push(',');
+        }
+        text.push_str(&format!("{{\"identifier\": {}, \"description\": \"item\", \"availability\": true}}", i));
+    }
+    text.push(']');
+
+    let plain    = Tape::parse(&text).unwrap();
+    let interned = Tape::parse_interned(&text).unwrap();
+
+    assert!(interned.heap_size() < plain.heap_size());
+
+    // Only the first occurrence of each key is stored.
+    let saved = 999 * ("identifier".len() + "description".len() + "availability".len());
+    assert_eq!(plain.strings_len() - interned.strings_len(), saved);
+
+    assert_eq!(interned.to_json(), plain.to_json());
+    assert_eq!(interned.root().at(500).unwrap().get("identifier").unwrap().as_number(), Some(&Number::Unsigned(500)));
+}
@@ -40,12 +40,12 @@ fn integer_to_string()
 #[test]
 fn float_to_string()
 {
-    test!(0.,    "0");
+    test!(0.,    "0.0");
     test!(1.1,   "1.1");
     test!(20.01, "20.01");
     test!(21.12, "21.12");
 
-    test!(-0.,    "0");
+    test!(-0.,    "-0.0");
     test!(-1.1,   "-1.1");
     test!(-20.01, "-20.01");
     test!(-21.12, "-21.12");
@@ -68,7 +68,7 @@ fn string_to_string()
 
 	test!(
         "I can escape some things, like \"\\/\u{0008}\u{000C}\n\r\t!",
-        "\"I can escape some things, like \\\"\\/\u{0008}\u{000C}\n\r\t!\""
+        "\"I can escape some things, like \\\"\\\\/\\b\\f\\n\\r\\t!\""
     );
 
 	test!(
@@ -80,15 +80,15 @@ fn string_to_string()
 #[test]
 fn array_to_string()
 {
-    test!(vec![], "[]");
+    test!(Vec::<Json>::new(), "[]");
 
     test!(
         vec![
             Json::from(1),
             Json::from(2.3),
             Json::from("String"), 
-            Json::from(vec![]),
-            Json::from(HashMap::new()),
+            Json::Array(vec![]),
+            Json::Object(HashMap::new()),
         ],
         "[1,2.3,\"String\",[],{}]"
     );
@@ -97,14 +97,14 @@ fn array_to_string()
 #[test]
 fn map_to_object()
 {
-    test!(HashMap::new(), "{}");
+    test!(HashMap::<String, Json>::new(), "{}");
 
     let mut map = HashMap::new();
     map.insert(String::from("i"), Json::from(-1));
     map.insert(String::from("f"), Json::from(2.3));
     map.insert(String::from("s"), Json::from("String"));
-    map.insert(String::from("a"), Json::from(vec![]));
-    map.insert(String::from("o"), Json::from(HashMap::new()));
+    map.insert(String::from("a"), Json::Array(vec![]));
+    map.insert(String::from("o"), Json::Object(HashMap::new()));
 
     assert_eq!(
         Json::from(map),
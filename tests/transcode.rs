@@ -0,0 +1,182 @@
+use std::io::{ self, Read };
+
+extern crate simple_json;
+use simple_json::{ transcode, Error, Format, Json, Position };
+
+fn minify(text: &[u8]) -> Result<String, Error>
+{
+    transcode(text, vec![], Format::new()).map(|output| String::from_utf8(output).unwrap())
+}
+
+fn syntax(line: usize, column: usize, offset: usize, error: Error) -> Error
+{
+    Error::Syntax(Position { line, column, offset }, Box::new(error))
+}
+
+#[test]
+fn minified_in_document_order()
+{
+    let text = "{ \"b\": [ 1, 2.5, \"x\\u00e9\\n\" ],\n  \"a\": { \"c\": null, \"d\": true, \"e\": {}, \"f\": [] } }\n";
+
+    assert_eq!(
+        minify(text.as_bytes()).unwrap(),
+        "{\"b\":[1,2.5,\"x\u{e9}\\n\"],\"a\":{\"c\":null,\"d\":true,\"e\":{},\"f\":[]}}"
+    );
+}
+
+#[test]
+fn pretty()
+{
+    let text = "{\"rows\":[{\"id\":1},{\"id\":2}],\"empty\":[],\"ok\":false}";
+
+    let output = transcode(text.as_bytes(), vec![], Format::pretty()).unwrap();
+
+    assert_eq!(String::from_utf8(output).unwrap(), "{
+  \"rows\": [
+    {
+      \"id\": 1
+    },
+    {
+      \"id\": 2
+    }
+  ],
+  \"empty\": [],
+  \"ok\": false
+}");
+}
+
+#[test]
+fn same_document()
+{
+    let text = "[{\"a\": -0, \"b\": 1e300, \"c\": 18446744073709551616, \"d\": \"\\ud83d\\ude00\"}, [[[]]], -12, 0.1]";
+
+    assert_eq!(Json::parse(&minify(text.as_bytes()).unwrap()), Json::parse(text));
+}
+
+#[test]
+fn scalar_roots()
+{
+    assert_eq!(minify(b" 12 ").unwrap(), "12");
+    assert_eq!(minify(b"\"text\"").unwrap(), "\"text\"");
+    assert_eq!(minify(b"null").unwrap(), "null");
+}
+
+#[test]
+fn error_positions()
+{
+    assert_eq!(minify(b"[1,\n  2,,3]"), Err(syntax(2, 5, 8, Error::InvalidCharacter(String::from(",")))));
+    assert_eq!(minify("[\"\u{e9}\", x]".as_bytes()), Err(syntax(1, 7, 7, Error::InvalidCharacter(String::from("x")))));
+    assert_eq!(minify(b"{\"a\": [1"), Err(syntax(1, 9, 8, Error::UnexpectedEof)));
+    assert_eq!(minify(b"1 2"), Err(syntax(1, 3, 2, Error::InvalidCharacter(String::from("2")))));
+    assert_eq!(minify(b""), Err(syntax(1, 1, 0, Error::UnexpectedEof)));
+    assert_eq!(minify(b"{\"a\" 1}"), Err(syntax(1, 6, 5, Error::InvalidCharacter(String::from("1")))));
+    assert_eq!(minify(b"{\"a\": 1,}"), Err(syntax(1, 9, 8, Error::InvalidCharacter(String::from("}")))));
+    assert_eq!(minify(b"[1}"), Err(syntax(1, 3, 2, Error::InvalidCharacter(String::from("}")))));
+}
+
+#[test]
+fn token_errors_point_at_the_token()
+{
+    assert_eq!(minify(b"[\"ab\\q\"]"), Err(syntax(1, 2, 1, Error::InvalidCharacter(String::from("q")))));
+    assert_eq!(minify(b"[1.]"), Err(syntax(1, 4, 3, Error::InvalidCharacter(String::from("]")))));
+    assert_eq!(minify(b"01"), Err(syntax(1, 1, 0, Error::InvalidCharacter(String::from("1")))));
+    assert_eq!(minify(b"[nul]"), Err(syntax(1, 2, 1, Error::InvalidCharacter(String::from("nul")))));
+    assert_eq!(minify(b"[\"abc"), Err(syntax(1, 6, 5, Error::UnexpectedEof)));
+}
+
+#[test]
+fn numbers_as_written()
+{
+    let text = "[1e400, 18446744073709551616, 3.14159265358979323846, 1E2, -0, 0.10, -5e-3]";
+
+    assert_eq!(minify(text.as_bytes()).unwrap(), text.replace(' ', ""));
+}
+
+#[test]
+fn control_characters()
+{
+    assert_eq!(minify(b"[\"a\nb\"]"), Err(syntax(1, 4, 3, Error::InvalidCharacter(String::from("U+000A")))));
+    assert_eq!(minify(b"{\"\x01\": 1}"), Err(syntax(1, 3, 2, Error::InvalidCharacter(String::from("U+0001")))));
+}
+
+#[test]
+fn invalid_utf8()
+{
+    assert_eq!(minify(b"[\"\xff\"]"), Err(syntax(1, 3, 2, Error::InvalidCharacter(String::from("\\xFF")))));
+    assert_eq!(minify(b"[\"\xc3\x28\"]"), Err(syntax(1, 3, 2, Error::InvalidCharacter(String::from("\\xC3")))));
+}
+
+// Produces `[{"id":0},{"id":1},...]` without ever holding it in memory.
+struct Rows {
+    count:   usize,
+    next:    usize,
+    pending: Vec<u8>,
+}
+
+impl Read for Rows
+{
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize>
+    {
+        if self.pending.is_empty() {
+            self.pending = match self.next {
+                0 => b"[".to_vec(),
+                n if n <= self.count => format!("{}{{\"id\":{}}}", if n > 1 { "," } else { "" }, n - 1).into_bytes(),
+                n if n == self.count + 1 => b"]".to_vec(),
+                _ => { return Ok(0); },
+            };
+            self.next += 1;
+        }
+
+        let length = self.pending.len().min(buffer.len());
+        buffer[..length].copy_from_slice(&self.pending[..length]);
+        self.pending.drain(..length);
+
+        Ok(length)
+    }
+}
+
+#[test]
+fn large_stream()
+{
+    struct Count(usize);
+
+    impl io::Write for Count
+    {
+        fn write(&mut self, buffer: &[u8]) -> io::Result<usize>
+        {
+            self.0 += buffer.len();
+            Ok(buffer.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()>
+        {
+            Ok(())
+        }
+    }
+
+    let rows   = Rows { count: 100_000, next: 0, pending: vec![] };
+    let output = transcode(rows, Count(0), Format::new()).unwrap();
+
+    let expect = Rows { count: 100_000, next: 0, pending: vec![] }.read_to_end(&mut vec![]).unwrap();
+
+    assert_eq!(output.0, expect);
+}
+
+#[test]
+fn read_errors()
+{
+    struct Failing;
+
+    impl Read for Failing
+    {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize>
+        {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "gone"))
+        }
+    }
+
+    assert_eq!(
+        transcode(Failing, vec![], Format::new()),
+        Err(Error::Io(io::ErrorKind::BrokenPipe, String::from("gone")))
+    );
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::convert::TryInto;
 
 extern crate simple_json;
 use simple_json::{ Json, Number };
@@ -6,7 +7,7 @@ use simple_json::{ Json, Number };
 macro_rules! test {
     ($T: ty, $jsn:expr, $val:expr) => {{
         let json = Json::parse($jsn).unwrap();
-        let val: $T = json.into();
+        let val: $T = json.try_into().unwrap();
         assert_eq!(val, $val);
     }};
 }
@@ -0,0 +1,139 @@
+extern crate simple_json;
+use simple_json::{ Format, Json, JsonWriter, KeyOrder };
+
+fn width(max_width: usize) -> Format
+{
+    Format {
+        max_width: Some(max_width),
+        sort_keys: KeyOrder::Lexicographic,
+        ..Format::pretty()
+    }
+}
+
+#[test]
+fn fits_on_one_line()
+{
+    let json = Json::parse("{\"points\": [[1.5, 2.5], [3, 4]], \"name\": \"path\"}").unwrap();
+
+    assert_eq!(
+        json.to_string_with(&width(80)).unwrap(),
+        "{ \"name\": \"path\", \"points\": [ [ 1.5, 2.5 ], [ 3, 4 ] ] }"
+    );
+}
+
+#[test]
+fn breaks_outer_groups_first()
+{
+    let json = Json::parse("{\"points\": [[1.5, 2.5], [3, 4]], \"name\": \"path\"}").unwrap();
+
+    assert_eq!(json.to_string_with(&width(30)).unwrap(), "{
+  \"name\": \"path\",
+  \"points\": [
+    [ 1.5, 2.5 ],
+    [ 3, 4 ]
+  ]
+}");
+
+    assert_eq!(json.to_string_with(&width(40)).unwrap(), "{
+  \"name\": \"path\",
+  \"points\": [ [ 1.5, 2.5 ], [ 3, 4 ] ]
+}");
+}
+
+#[test]
+fn exact_width()
+{
+    let json = Json::parse("[1, 2, 3]").unwrap();
+
+    assert_eq!(json.to_string_with(&width(11)).unwrap(), "[ 1, 2, 3 ]");
+    assert_eq!(json.to_string_with(&width(10)).unwrap(), "[\n  1,\n  2,\n  3\n]");
+}
+
+#[test]
+fn trailing_comma_counts()
+{
+    // The first element needs a comma after it, the last one doesn't.
+    let json = Json::parse("[[1, 2], [3, 4]]").unwrap();
+
+    assert_eq!(json.to_string_with(&width(10)).unwrap(), "[\n  [\n    1,\n    2\n  ],\n  [ 3, 4 ]\n]");
+}
+
+#[test]
+fn readme_example()
+{
+    let text = "{ \"integer\": 12, \"float\": 80.5, \"string\": \"A JSON sample\", \"array\": [ 1, 2, 3 ], \"object\": { \"a\": \"b\" } }";
+    let json = Json::parse(text).unwrap();
+
+    assert_eq!(json.to_string_with(&width(80)).unwrap(), "{
+  \"array\": [ 1, 2, 3 ],
+  \"float\":   80.5,
+  \"integer\": 12,
+  \"object\": { \"a\": \"b\" },
+  \"string\":  \"A JSON sample\"
+}");
+}
+
+#[test]
+fn long_scalars_overflow()
+{
+    let json = Json::parse("[\"a string longer than the width\"]").unwrap();
+
+    assert_eq!(
+        json.to_string_with(&width(10)).unwrap(),
+        "[\n  \"a string longer than the width\"\n]"
+    );
+}
+
+#[test]
+fn empty_containers()
+{
+    let json = Json::parse("{\"a\": [], \"b\": {}}").unwrap();
+
+    assert_eq!(json.to_string_with(&width(20)).unwrap(), "{ \"a\": [], \"b\": {} }");
+    assert_eq!(json.to_string_with(&width(10)).unwrap(), "{\n  \"a\": [],\n  \"b\": {}\n}");
+}
+
+#[test]
+fn ignored_when_compact()
+{
+    let json = Json::parse("[[1, 2], {\"a\": null}]").unwrap();
+
+    let format = Format { max_width: Some(1), ..Format::new() };
+
+    assert_eq!(json.to_string_with(&format).unwrap(), "[[1,2],{\"a\":null}]");
+}
+
+#[test]
+fn round_trip()
+{
+    let text = "{\"rows\": [{\"id\": 1, \"tags\": [\"x\", \"y\"]}, {\"id\": 2, \"tags\": []}], \"total\": 2}";
+    let json = Json::parse(text).unwrap();
+
+    for max_width in 0..80 {
+        assert_eq!(Json::parse(&json.to_string_with(&width(max_width)).unwrap()), Ok(json.clone()));
+    }
+}
+
+#[test]
+fn writer_values()
+{
+    let mut writer = JsonWriter::new(vec![], width(20));
+
+    writer.begin_object().unwrap();
+    writer.key("short").unwrap();
+    writer.value(&Json::parse("[1, 2]").unwrap()).unwrap();
+    writer.key("long").unwrap();
+    writer.value(&Json::parse("[1, 2, 3]").unwrap()).unwrap();
+    writer.end_object().unwrap();
+
+    let text = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+    assert_eq!(text, "{
+  \"short\": [ 1, 2 ],
+  \"long\": [
+    1,
+    2,
+    3
+  ]
+}");
+}
@@ -0,0 +1,94 @@
+use std::io;
+
+extern crate simple_json;
+use simple_json::{ Error, Format, Json, NonFinite, Number };
+
+const TEXT: &str = "[{\"a\": [1, -2, 3.5]}, \"str\\\"ing\", true, null, {}]";
+
+struct Failing(usize);
+
+impl io::Write for Failing
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize>
+    {
+        if self.0 < buf.len() {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+        }
+
+        self.0 -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()>
+    {
+        Ok(())
+    }
+}
+
+#[test]
+fn write_to_io()
+{
+    let json = Json::parse(TEXT).unwrap();
+
+    let mut buffer = vec![];
+    json.write_to(&mut buffer, &Format::default()).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), json.to_string());
+
+    let mut buffer = vec![];
+    json.write_to(&mut buffer, &Format::pretty()).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), json.to_pretty_string());
+}
+
+#[test]
+fn write_to_io_errors()
+{
+    let json = Json::parse(TEXT).unwrap();
+
+    let result = json.write_to(&mut Failing(10), &Format::default());
+    assert_eq!(result, Err(Error::Io(io::ErrorKind::BrokenPipe, String::from("pipe closed"))));
+
+    let format = Format { non_finite: NonFinite::Error, ..Format::default() };
+    let result = Json::from(f64::NAN).write_to(&mut vec![], &format);
+    assert_eq!(result, Err(Error::NonFiniteNumber(String::from("NaN"))));
+}
+
+#[test]
+fn format_to_fmt()
+{
+    let json = Json::parse(TEXT).unwrap();
+
+    let mut string = String::from("prefix ");
+    json.format_to(&mut string, &Format::default()).unwrap();
+
+    assert_eq!(string, format!("prefix {}", json));
+}
+
+#[test]
+fn display()
+{
+    let json = Json::parse("{\"a\": [1, 2.0, \"x\"]}").unwrap();
+
+    assert_eq!(format!("{}", json), "{\"a\":[1,2.0,\"x\"]}");
+    assert_eq!(format!("{:#}", json), "{\n  \"a\": [ 1, 2.0, \"x\" ]\n}");
+    assert_eq!(json.to_string(), "{\"a\":[1,2.0,\"x\"]}");
+
+    assert_eq!(format!("{}", Number::Unsigned(7)), "7");
+    assert_eq!(format!("{}", Number::Integer(-7)), "-7");
+    assert_eq!(format!("{}", Number::Float(7.)), "7.0");
+    assert_eq!(Number::Float(f64::NEG_INFINITY).to_string(), "-Infinity");
+}
+
+#[test]
+fn deep_nesting()
+{
+    let mut json = Json::Null;
+
+    for _ in 0..500 {
+        json = Json::Array(vec![json]);
+    }
+
+    let text = json.to_string();
+
+    assert_eq!(text.len(), 500 * 2 + 4);
+    assert!(text.starts_with("[[[[") && text.contains("[null]") && text.ends_with("]]]]"));
+}
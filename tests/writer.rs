@@ -0,0 +1,157 @@
+use std::io;
+
+extern crate simple_json;
+use simple_json::{ Error, Format, Json, JsonWriter, NonFinite, Number };
+
+fn output(writer: JsonWriter<Vec<u8>>) -> String
+{
+    String::from_utf8(writer.finish().unwrap()).unwrap()
+}
+
+#[test]
+fn compact_document()
+{
+    let mut writer = JsonWriter::new(vec![], Format::default());
+
+    writer.begin_object().unwrap();
+    writer.key("rows").unwrap();
+    writer.begin_array().unwrap();
+
+    for i in 0..3 {
+        writer.begin_object().unwrap();
+        writer.key("id").unwrap();
+        writer.number(Number::Unsigned(i)).unwrap();
+        writer.key("name").unwrap();
+        writer.string("row \"quoted\"").unwrap();
+        writer.end_object().unwrap();
+    }
+
+    writer.end_array().unwrap();
+    writer.key("empty").unwrap();
+    writer.begin_array().unwrap();
+    writer.end_array().unwrap();
+    writer.key("done").unwrap();
+    writer.boolean(true).unwrap();
+    writer.key("next").unwrap();
+    writer.null().unwrap();
+    writer.end_object().unwrap();
+
+    let text = output(writer);
+
+    assert_eq!(text, "{\"rows\":[{\"id\":0,\"name\":\"row \\\"quoted\\\"\"},{\"id\":1,\"name\":\"row \\\"quoted\\\"\"},{\"id\":2,\"name\":\"row \\\"quoted\\\"\"}],\"empty\":[],\"done\":true,\"next\":null}");
+    assert!(Json::parse(&text).is_ok());
+}
+
+#[test]
+fn pretty_document()
+{
+    let mut writer = JsonWriter::new(vec![], Format::pretty());
+
+    writer.begin_array().unwrap();
+    writer.value(&Json::parse("{\"a\": [1, 2]}").unwrap()).unwrap();
+    writer.begin_object().unwrap();
+    writer.end_object().unwrap();
+    writer.begin_object().unwrap();
+    writer.key("b").unwrap();
+    writer.number(Number::Float(1.)).unwrap();
+    writer.end_object().unwrap();
+    writer.end_array().unwrap();
+
+    assert_eq!(output(writer), "[\n  {\n    \"a\": [ 1, 2 ]\n  },\n  {},\n  {\n    \"b\": 1.0\n  }\n]");
+}
+
+#[test]
+fn shared_escaping()
+{
+    let format = Format { ascii_only: true, html_safe: true, ..Format::default() };
+
+    let mut writer = JsonWriter::new(vec![], format);
+    writer.begin_object().unwrap();
+    writer.key("<é>").unwrap();
+    writer.string("😀").unwrap();
+    writer.end_object().unwrap();
+
+    assert_eq!(output(writer), "{\"\\u003c\\u00e9\\u003e\":\"\\ud83d\\ude00\"}");
+}
+
+#[test]
+fn invalid_call_order()
+{
+    let mut writer = JsonWriter::new(vec![], Format::default());
+    assert!(writer.key("a").is_err());
+    assert!(writer.end_array().is_err());
+
+    let mut writer = JsonWriter::new(vec![], Format::default());
+    writer.begin_object().unwrap();
+    assert_eq!(writer.null(), Err(Error::InvalidCall(String::from("expecting a key, got a value"))));
+    writer.key("a").unwrap();
+    assert!(writer.key("b").is_err());
+    assert!(writer.end_object().is_err());
+    writer.null().unwrap();
+    assert!(writer.end_array().is_err());
+    writer.end_object().unwrap();
+    assert_eq!(writer.null(), Err(Error::InvalidCall(String::from("more than one root value"))));
+
+    let mut writer = JsonWriter::new(vec![], Format::default());
+    writer.begin_array().unwrap();
+    assert!(writer.key("a").is_err());
+    assert_eq!(writer.finish().err(), Some(Error::InvalidCall(String::from("unclosed container"))));
+
+    let writer = JsonWriter::new(vec![], Format::default());
+    assert_eq!(writer.finish().err(), Some(Error::InvalidCall(String::from("empty document"))));
+}
+
+#[test]
+fn invalid_value_changes_nothing()
+{
+    let format = Format { non_finite: NonFinite::Error, ..Format::pretty() };
+
+    let mut writer = JsonWriter::new(vec![], format);
+    writer.begin_array().unwrap();
+    writer.number(Number::Unsigned(0)).unwrap();
+    assert!(writer.number(Number::Float(f64::INFINITY)).is_err());
+    writer.begin_object().unwrap();
+    writer.key("a").unwrap();
+    assert!(writer.number(Number::Float(f64::NAN)).is_err());
+    assert!(writer.key("b").is_err());
+    writer.number(Number::Unsigned(1)).unwrap();
+    writer.end_object().unwrap();
+    writer.end_array().unwrap();
+
+    assert_eq!(output(writer), "[\n  0,\n  {\n    \"a\": 1\n  }\n]");
+}
+
+// Accepts a few bytes, then fails.
+struct Full(usize);
+
+impl io::Write for Full
+{
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize>
+    {
+        if self.0 == 0 {
+            return Err(io::Error::from(io::ErrorKind::WriteZero));
+        }
+
+        let written = buffer.len().min(self.0);
+        self.0 -= written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()>
+    {
+        Ok(())
+    }
+}
+
+#[test]
+fn failed_writer_stays_failed()
+{
+    let mut writer = JsonWriter::new(Full(4), Format::default());
+    writer.begin_object().unwrap();
+    assert_eq!(writer.key("long key"), Err(Error::Io(io::ErrorKind::WriteZero, io::Error::from(io::ErrorKind::WriteZero).to_string())));
+
+    let failed = Err(Error::InvalidCall(String::from("writer failed on an earlier call")));
+    assert_eq!(writer.key("b"), failed);
+    assert_eq!(writer.end_object(), failed);
+    assert_eq!(writer.finish().err(), failed.err());
+}